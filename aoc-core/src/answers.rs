@@ -0,0 +1,155 @@
+use crate::input::{self, InputConfig};
+use crate::{Error, Result};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const ANSWERS_FILE_NAME: &str = "answers.txt";
+
+/// The known answers for a day's real input, kept next to `input/` in
+/// `aocNN/answers.txt` as `part1: <answer>` and `part2: <answer>` lines.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecordedAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl RecordedAnswers {
+    pub fn parse(text: &str) -> Result<RecordedAnswers> {
+        let mut answers = RecordedAnswers::default();
+        for field in fields(text)? {
+            if !answers.set_field(&field) {
+                return Err(
+                    Error::parse("expected `part1: <answer>` or `part2: <answer>`")
+                        .on_line(field.line, field.text),
+                );
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Takes `part1` and `part2` fields, leaving anything else to the caller.
+    pub(crate) fn set_field(&mut self, field: &Field) -> bool {
+        match field.name {
+            "part1" => self.set(1, field.value.to_string()),
+            "part2" => self.set(2, field.value.to_string()),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, value: String) {
+        match part {
+            1 => self.part_one = Some(value),
+            2 => self.part_two = Some(value),
+            _ => {}
+        }
+    }
+}
+
+/// A `name: value` line of an answers file.
+pub(crate) struct Field<'a> {
+    pub line: usize,
+    pub text: &'a str,
+    pub name: &'a str,
+    pub value: &'a str,
+}
+
+/// Every field in `text`, skipping blank lines and `#` comments.
+pub(crate) fn fields(text: &str) -> Result<Vec<Field<'_>>> {
+    let mut fields = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        match trimmed.split_once(':') {
+            Some((name, value)) => fields.push(Field {
+                line: index + 1,
+                text: line,
+                name: name.trim(),
+                value: value.trim(),
+            }),
+            None => return Err(Error::parse("expected `<name>: <value>`").on_line(index + 1, line)),
+        }
+    }
+    Ok(fields)
+}
+
+impl fmt::Display for RecordedAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(value) = &self.part_one {
+            writeln!(f, "part1: {}", value)?;
+        }
+        if let Some(value) = &self.part_two {
+            writeln!(f, "part2: {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl InputConfig {
+    pub fn answers_path(&self, day: u8) -> PathBuf {
+        self.day_dir(day).join(ANSWERS_FILE_NAME)
+    }
+}
+
+/// Reads an answers file. A missing file just means nothing is recorded yet.
+pub fn read_answers(path: &Path) -> Result<RecordedAnswers> {
+    match fs::read_to_string(path) {
+        Ok(text) => RecordedAnswers::parse(&text),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(RecordedAnswers::default()),
+        Err(source) => Err(input::InputError::Unreadable {
+            path: path.to_path_buf(),
+            source,
+        }
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn can_parse_answers() {
+        let answers = RecordedAnswers::parse("# day 3\npart1: 7\n\npart2:336\n").unwrap();
+        assert_eq!(Some("7"), answers.get(1));
+        assert_eq!(Some("336"), answers.get(2));
+        assert_eq!("part1: 7\npart2: 336\n", answers.to_string());
+
+        let partial = RecordedAnswers::parse("part2: 62").unwrap();
+        assert_eq!(None, partial.get(1));
+        assert_eq!("part2: 62\n", partial.to_string());
+    }
+
+    #[test]
+    fn rejects_unknown_lines() {
+        let error = RecordedAnswers::parse("part1: 7\npart3: 9").unwrap_err();
+        assert_eq!(Some(2), error.line);
+    }
+
+    #[test]
+    fn can_round_trip_answers_file() {
+        let path = env::temp_dir().join(format!("aoc-core-answers-{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(RecordedAnswers::default(), read_answers(&path).unwrap());
+
+        let mut answers = RecordedAnswers::default();
+        answers.set(1, "514579".to_string());
+        fs::write(&path, answers.to_string()).unwrap();
+        assert_eq!(answers, read_answers(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+    }
+}
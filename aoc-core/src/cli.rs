@@ -0,0 +1,171 @@
+use crate::error;
+use crate::input::InputConfig;
+use crate::output::{self, Format};
+use crate::trace;
+use crate::{Error, Solver};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+/// Command line of a per-day binary:
+/// `aocNN [input] [--part 1|2] [--format f] [--explain] [-v|-vv] [--trace-json path]`.
+#[derive(Debug, PartialEq)]
+pub struct DayArgs {
+    pub path: Option<PathBuf>,
+    pub part: Option<u8>,
+    pub format: Format,
+    pub explain: bool,
+    /// How many `-v`s were given.
+    pub verbosity: u8,
+    pub trace_json: Option<PathBuf>,
+}
+
+impl DayArgs {
+    pub fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+pub fn parse_day_args<I: IntoIterator<Item = String>>(args: I) -> Result<DayArgs, String> {
+    let mut parsed = DayArgs {
+        path: None,
+        part: None,
+        format: Format::Text,
+        explain: false,
+        verbosity: 0,
+        trace_json: None,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parsed.part = Some(parse_part(args.next())?),
+            "--format" | "-f" => parsed.format = parse_format(args.next())?,
+            "--explain" | "-e" => parsed.explain = true,
+            "--verbose" | "-v" => parsed.verbosity += 1,
+            "-vv" => parsed.verbosity += 2,
+            "--trace-json" => match args.next() {
+                Some(path) => parsed.trace_json = Some(PathBuf::from(path)),
+                None => return Err("--trace-json needs a path".to_string()),
+            },
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ if parsed.path.is_none() => parsed.path = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(parsed)
+}
+
+pub fn parse_part(part: Option<String>) -> Result<u8, String> {
+    match part.as_deref() {
+        Some("1") => Ok(1),
+        Some("2") => Ok(2),
+        Some(other) => Err(format!("'{}' is not a part, expected 1 or 2", other)),
+        None => Err("--part needs 1 or 2".to_string()),
+    }
+}
+
+pub fn parse_format(format: Option<String>) -> Result<Format, String> {
+    match format {
+        Some(format) => format.parse(),
+        None => Err("--format needs text, json or tsv".to_string()),
+    }
+}
+
+/// The whole `main` of a per-day binary. `descriptions` label the two parts
+/// in text output, e.g. `Part 1 trees: 7`.
+pub fn main<S: Solver>(solver: &S, descriptions: [&str; 2]) {
+    let day = solver.day();
+    let args = match parse_day_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!(
+                "error: {}\n\nUsage: aoc{:02} [input] [--part 1|2] [--format text|json|tsv] [--explain]\n             [-v|-vv] [--trace-json <path>]",
+                message, day
+            );
+            process::exit(2);
+        }
+    };
+    if let Err(e) = trace::start_from_args(args.verbosity, args.trace_json.as_deref()) {
+        eprintln!("error: cannot start tracing: {}", e);
+        process::exit(1);
+    }
+
+    let answers = InputConfig::from_env()
+        .read_input(day, args.path.as_deref())
+        .map_err(Error::from)
+        .and_then(|input| {
+            if args.explain {
+                solver.explain(&input, &args.parts())
+            } else {
+                solver.solve(&input, &args.parts())
+            }
+        });
+    if let Err(e) = trace::stop() {
+        eprintln!("error: cannot write the trace: {}", e);
+    }
+    match answers {
+        Ok(answers) => match args.format {
+            Format::Text => {
+                for answer in answers {
+                    let description = descriptions[usize::from(answer.part) - 1];
+                    output::print_answer(answer.part, description, &answer.value);
+                    if let Some(explanation) = answer.explanation {
+                        print!("{}", output::format_explanation(&explanation));
+                    }
+                }
+            }
+            Format::Json => print!("{}", output::format_json(&answers)),
+            Format::Tsv => print!("{}", output::format_tsv(&answers)),
+        },
+        Err(e) => error::exit_on_error(day, Err(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn can_parse_day_args() {
+        assert_eq!(
+            Ok(DayArgs {
+                path: None,
+                part: None,
+                format: Format::Text,
+                explain: false,
+                verbosity: 0,
+                trace_json: None
+            }),
+            parse_day_args(args(""))
+        );
+        assert_eq!(
+            Ok(DayArgs {
+                path: Some(PathBuf::from("input/test.txt")),
+                part: Some(2),
+                format: Format::Json,
+                explain: true,
+                verbosity: 3,
+                trace_json: Some(PathBuf::from("trace.jsonl"))
+            }),
+            parse_day_args(args(
+                "input/test.txt --part 2 --format json --explain -v -vv --trace-json trace.jsonl"
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_day_args() {
+        assert!(parse_day_args(args("--part 3")).is_err());
+        assert!(parse_day_args(args("--format")).is_err());
+        assert!(parse_day_args(args("--quiet")).is_err());
+        assert!(parse_day_args(args("--trace-json")).is_err());
+        assert!(parse_day_args(args("a.txt b.txt")).is_err());
+    }
+}
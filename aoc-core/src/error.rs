@@ -0,0 +1,168 @@
+use crate::input::InputError;
+use std::fmt;
+use std::process;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Everything that can go wrong between reading the input and printing an
+/// answer. Line and column numbers start at 1 and, like the day, are filled
+/// in by whichever layer knows them.
+#[derive(Debug)]
+pub struct Error {
+    pub day: Option<u8>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: Option<String>,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// The input does not look like the puzzle says it should.
+    Parse(String),
+    /// The input parsed fine but has no answer, e.g. no entries sum to 2020.
+    NoSolution(String),
+    /// An answer or an intermediate value does not fit its integer type.
+    Overflow(String),
+    Input(InputError),
+}
+
+impl Error {
+    fn new(kind: ErrorKind) -> Error {
+        Error {
+            day: None,
+            line: None,
+            column: None,
+            text: None,
+            kind,
+        }
+    }
+
+    pub fn parse<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::Parse(message.into()))
+    }
+
+    pub fn no_solution<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::NoSolution(message.into()))
+    }
+
+    pub fn overflow<S: Into<String>>(message: S) -> Error {
+        Error::new(ErrorKind::Overflow(message.into()))
+    }
+
+    pub fn in_day(mut self, day: u8) -> Error {
+        self.day.get_or_insert(day);
+        self
+    }
+
+    /// Records the line the error happened on, unless a more precise layer
+    /// already did.
+    pub fn on_line(mut self, line: usize, text: &str) -> Error {
+        if self.line.is_none() {
+            self.line = Some(line);
+            self.text = Some(text.to_string());
+        }
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> Error {
+        self.column.get_or_insert(column);
+        self
+    }
+
+    pub fn with_text(mut self, text: &str) -> Error {
+        self.text.get_or_insert_with(|| text.to_string());
+        self
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(e: InputError) -> Error {
+        Error::new(ErrorKind::Input(e))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut location = Vec::new();
+        if let Some(day) = self.day {
+            location.push(format!("day {}", day));
+        }
+        if let Some(line) = self.line {
+            location.push(format!("line {}", line));
+        }
+        if let Some(column) = self.column {
+            location.push(format!("column {}", column));
+        }
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+
+        match &self.kind {
+            ErrorKind::Parse(message)
+            | ErrorKind::NoSolution(message)
+            | ErrorKind::Overflow(message) => write!(f, "{}", message)?,
+            ErrorKind::Input(e) => write!(f, "{}", e)?,
+        }
+
+        if let Some(text) = &self.text {
+            write!(f, "\n    {}", text)?;
+            if let Some(column) = self.column {
+                write!(f, "\n    {:>width$}", "^", width = column)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Input(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Ends a per-day binary: prints the diagnostic and exits non-zero on error.
+pub fn exit_on_error(day: u8, result: Result<()>) {
+    if let Err(e) = result {
+        eprintln!("error: {}", e.in_day(day));
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_display_parse_error() {
+        let error = Error::parse("expected a number")
+            .at_column(3)
+            .on_line(5, "1-x a: abc")
+            .in_day(2);
+        assert_eq!(
+            "day 2, line 5, column 3: expected a number\n    1-x a: abc\n      ^",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_the_most_precise_location() {
+        let error = Error::parse("bad seat")
+            .on_line(2, "FBFBBFFRLX")
+            .on_line(1, "FBFBBFFRLX\nFBFBBFFRLX")
+            .in_day(5)
+            .in_day(1);
+        assert_eq!(Some(5), error.day);
+        assert_eq!(Some(2), error.line);
+        assert_eq!(Some("FBFBBFFRLX".to_string()), error.text);
+    }
+
+    #[test]
+    fn can_display_no_solution() {
+        let error = Error::no_solution("no two entries sum to 2020").in_day(1);
+        assert_eq!("day 1: no two entries sum to 2020", error.to_string());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::answers::{self, RecordedAnswers};
+use crate::input::InputError;
+use crate::{Error, Result, Solver};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+/// A sample input `input/<name>.txt` with its expected answers beside it in
+/// `input/<name>.answers`. The answers file uses the `answers.txt` format and
+/// may add `name: value` settings for the solver, e.g. `preamble: 5`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixture {
+    pub path: PathBuf,
+    pub input: String,
+    pub expected: RecordedAnswers,
+    pub settings: Vec<(String, String)>,
+}
+
+impl Fixture {
+    pub fn parse(path: PathBuf, input: String, answers: &str) -> Result<Fixture> {
+        let mut expected = RecordedAnswers::default();
+        let mut settings = Vec::new();
+        for field in answers::fields(answers)? {
+            if !expected.set_field(&field) {
+                settings.push((field.name.to_string(), field.value.to_string()));
+            }
+        }
+        Ok(Fixture {
+            path,
+            input,
+            expected,
+            settings,
+        })
+    }
+
+    /// Applies the settings to `solver`, solves every part with an expected
+    /// answer and describes each one that came out different.
+    pub fn check(&self, solver: &mut dyn Solver) -> std::result::Result<(), String> {
+        let name = self.path.display();
+        for (setting, value) in self.settings.iter() {
+            solver
+                .configure(setting, value)
+                .map_err(|e| format!("{}: {}", name, e))?;
+        }
+        let parts: Vec<u8> = [1, 2]
+            .iter()
+            .cloned()
+            .filter(|&part| self.expected.get(part).is_some())
+            .collect();
+        let answers = solver
+            .solve(&self.input, &parts)
+            .map_err(|e| format!("{}: {}", name, e))?;
+
+        let mismatches: Vec<String> = answers
+            .iter()
+            .filter_map(|answer| match self.expected.get(answer.part) {
+                Some(expected) if expected != answer.value => Some(format!(
+                    "{}: part {} expected {}, got {}",
+                    name, answer.part, expected, answer.value
+                )),
+                _ => None,
+            })
+            .collect();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches.join("\n"))
+        }
+    }
+}
+
+/// Every fixture in `input_dir`, in file name order. A missing directory has
+/// no fixtures; an answers file without its input is an error.
+pub fn load_fixtures(input_dir: &Path) -> Result<Vec<Fixture>> {
+    let entries = match fs::read_dir(input_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(unreadable(input_dir, source)),
+    };
+    let mut answer_paths = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| unreadable(input_dir, e))?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some(ANSWERS_EXTENSION) {
+            answer_paths.push(path);
+        }
+    }
+    answer_paths.sort();
+
+    let mut fixtures = Vec::new();
+    for answers_path in answer_paths {
+        let input_path = answers_path.with_extension("txt");
+        let answers = read(&answers_path)?;
+        let input = read(&input_path)?;
+        let fixture = Fixture::parse(input_path, input, &answers)
+            .map_err(|e| Error::parse(format!("{}: {}", answers_path.display(), e)))?;
+        fixtures.push(fixture);
+    }
+    Ok(fixtures)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|source| unreadable(path, source))
+}
+
+fn unreadable(path: &Path, source: io::Error) -> Error {
+    InputError::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+    use std::env;
+    use std::process;
+
+    struct Sum {
+        offset: i64,
+    }
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+        type PartOne = i64;
+        type PartTwo = i64;
+
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<i64>> {
+            crate::parse::numbers(input)
+        }
+
+        fn part_one(&self, numbers: &Vec<i64>) -> Result<i64> {
+            Ok(numbers.iter().sum::<i64>() + self.offset)
+        }
+
+        fn part_two(&self, numbers: &Vec<i64>) -> Result<i64> {
+            Ok(numbers.iter().product::<i64>() + self.offset)
+        }
+
+        fn configure(&mut self, name: &str, value: &str) -> Result<()> {
+            match name {
+                "offset" => self.offset = value.parse().map_err(|_| Error::parse("bad offset"))?,
+                _ => return Err(Error::parse(format!("unknown setting `{}`", name))),
+            }
+            Ok(())
+        }
+    }
+
+    fn fixture(answers: &str) -> Fixture {
+        Fixture::parse(PathBuf::from("test.txt"), "2\n3\n".to_string(), answers).unwrap()
+    }
+
+    #[test]
+    fn can_parse_fixture() {
+        let fixture = fixture("# sample\npart1: 5\noffset: 1\n");
+        assert_eq!(Some("5"), fixture.expected.get(1));
+        assert_eq!(None, fixture.expected.get(2));
+        assert_eq!(
+            vec![("offset".to_string(), "1".to_string())],
+            fixture.settings
+        );
+    }
+
+    #[test]
+    fn can_check_fixture() {
+        assert_eq!(
+            Ok(()),
+            fixture("part1: 5\npart2: 6").check(&mut Sum { offset: 0 })
+        );
+        assert_eq!(
+            Ok(()),
+            fixture("part1: 6\noffset: 1").check(&mut Sum { offset: 0 })
+        );
+        assert_eq!(
+            Err("test.txt: part 2 expected 7, got 6".to_string()),
+            fixture("part1: 5\npart2: 7").check(&mut Sum { offset: 0 })
+        );
+        assert!(fixture("preamble: 5")
+            .check(&mut Sum { offset: 0 })
+            .is_err());
+    }
+
+    #[test]
+    fn can_load_fixtures() {
+        let dir = env::temp_dir().join(format!("aoc-core-fixtures-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input.txt"), "1\n").unwrap();
+        fs::write(dir.join("test.txt"), "2\n3\n").unwrap();
+        fs::write(dir.join("test.answers"), "part1: 5\n").unwrap();
+        fs::write(dir.join("test2.txt"), "4\n").unwrap();
+        fs::write(dir.join("test2.answers"), "part2: 4\n").unwrap();
+
+        let fixtures = load_fixtures(&dir).unwrap();
+        let names: Vec<&Path> = fixtures.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(vec![dir.join("test.txt"), dir.join("test2.txt")], names);
+
+        fs::write(dir.join("orphan.answers"), "part1: 1\n").unwrap();
+        assert!(load_fixtures(&dir).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Vec::<Fixture>::new(), load_fixtures(&dir).unwrap());
+    }
+}
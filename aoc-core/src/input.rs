@@ -0,0 +1,221 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_FILE_NAME: &str = "input.txt";
+
+/// Where to look for a day's input when no path is given: the file named by
+/// `file_name` inside `<root>/aocNN/input/`. `{day}` and `{day:02}` in the
+/// file name are replaced with the plain and zero-padded day number.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputConfig {
+    pub root: PathBuf,
+    pub file_name: String,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Unreadable { path: PathBuf, source: io::Error },
+    Stdin(io::Error),
+    NotFound { day: u8, searched: PathBuf },
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        InputConfig {
+            root: Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .expect("aoc-core lives inside the workspace")
+                .to_path_buf(),
+            file_name: DEFAULT_FILE_NAME.to_string(),
+        }
+    }
+}
+
+impl InputConfig {
+    /// The default convention, overridden by `AOC_ROOT` and `AOC_INPUT_NAME`.
+    pub fn from_env() -> InputConfig {
+        let mut config = InputConfig::default();
+        if let Some(root) = env::var_os("AOC_ROOT") {
+            config.root = PathBuf::from(root);
+        }
+        if let Ok(file_name) = env::var("AOC_INPUT_NAME") {
+            config.file_name = file_name;
+        }
+        config
+    }
+
+    pub fn day_dir(&self, day: u8) -> PathBuf {
+        self.root.join(format!("aoc{:02}", day))
+    }
+
+    pub fn input_dir(&self, day: u8) -> PathBuf {
+        self.day_dir(day).join("input")
+    }
+
+    pub fn default_path(&self, day: u8) -> PathBuf {
+        self.input_dir(day)
+            .join(render_file_name(&self.file_name, day))
+    }
+
+    /// Reads `path` if one was given, then the conventional file in the day's
+    /// `input/` directory, and only then stdin (unless it is a terminal).
+    pub fn read_input(&self, day: u8, path: Option<&Path>) -> Result<String, InputError> {
+        let stdin = io::stdin();
+        if stdin.is_terminal() {
+            self.read_input_from(day, path, None)
+        } else {
+            self.read_input_from(day, path, Some(&mut stdin.lock()))
+        }
+    }
+
+    /// Like [`read_input`](InputConfig::read_input) without the stdin fallback.
+    pub fn read_file(&self, day: u8, path: Option<&Path>) -> Result<String, InputError> {
+        self.read_input_from(day, path, None)
+    }
+
+    fn read_input_from(
+        &self,
+        day: u8,
+        path: Option<&Path>,
+        stdin: Option<&mut dyn Read>,
+    ) -> Result<String, InputError> {
+        if let Some(path) = path {
+            return read_path(path);
+        }
+        let default_path = self.default_path(day);
+        if default_path.is_file() {
+            return read_path(&default_path);
+        }
+        match stdin {
+            Some(stdin) => {
+                let mut input = String::new();
+                stdin
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            None => Err(InputError::NotFound {
+                day,
+                searched: default_path,
+            }),
+        }
+    }
+}
+
+pub fn render_file_name(pattern: &str, day: u8) -> String {
+    pattern
+        .replace("{day:02}", &format!("{:02}", day))
+        .replace("{day}", &day.to_string())
+}
+
+fn read_path(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Unreadable { path, source } => {
+                write!(f, "cannot read {}: {}", path.display(), source)
+            }
+            InputError::Stdin(source) => write!(f, "cannot read stdin: {}", source),
+            InputError::NotFound { day, searched } => write!(
+                f,
+                "no input for day {}: no path given, {} does not exist and nothing was piped in",
+                day,
+                searched.display()
+            ),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Unreadable { source, .. } | InputError::Stdin(source) => Some(source),
+            InputError::NotFound { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn scratch_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-core-input-{}-{}", name, process::id()));
+        fs::create_dir_all(root.join("aoc07").join("input")).unwrap();
+        root
+    }
+
+    #[test]
+    fn can_render_file_name() {
+        assert_eq!("input.txt", render_file_name("input.txt", 7));
+        assert_eq!("day7.txt", render_file_name("day{day}.txt", 7));
+        assert_eq!("day07.txt", render_file_name("day{day:02}.txt", 7));
+    }
+
+    #[test]
+    fn can_read_explicit_path_first() {
+        let root = scratch_root("explicit");
+        let explicit = root.join("other.txt");
+        fs::write(&explicit, "explicit").unwrap();
+        fs::write(root.join("aoc07/input/input.txt"), "conventional").unwrap();
+        let config = InputConfig {
+            root,
+            file_name: DEFAULT_FILE_NAME.to_string(),
+        };
+
+        let mut stdin: &[u8] = b"stdin";
+        assert_eq!(
+            "explicit",
+            config
+                .read_input_from(7, Some(&explicit), Some(&mut stdin))
+                .unwrap()
+        );
+        assert_eq!(
+            "conventional",
+            config.read_input_from(7, None, Some(&mut stdin)).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_fall_back_to_stdin() {
+        let config = InputConfig {
+            root: scratch_root("stdin"),
+            file_name: "day{day:02}.txt".to_string(),
+        };
+
+        let mut stdin: &[u8] = b"stdin";
+        assert_eq!(
+            "stdin",
+            config.read_input_from(7, None, Some(&mut stdin)).unwrap()
+        );
+        match config.read_input_from(7, None, None) {
+            Err(InputError::NotFound { day, searched }) => {
+                assert_eq!(7, day);
+                assert!(searched.ends_with("aoc07/input/day07.txt"));
+            }
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_missing_explicit_path() {
+        let config = InputConfig::default();
+        let mut stdin: &[u8] = b"stdin";
+        let missing = Path::new("/nonexistent/aoc/input.txt");
+        assert!(matches!(
+            config.read_input_from(1, Some(missing), Some(&mut stdin)),
+            Err(InputError::Unreadable { .. })
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::{Error, Result};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// The integer types a day can work in. Arithmetic goes through the `try_`
+/// methods, which report overflow as an [`Error`] instead of wrapping.
+pub trait Int:
+    Copy + Ord + Hash + Debug + Display + FromStr<Err = ParseIntError> + From<u16> + TryFrom<usize>
+{
+    const NAME: &'static str;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn try_add(self, rhs: Self) -> Result<Self> {
+        self.checked_add(rhs)
+            .ok_or_else(|| overflow(self, "+", rhs))
+    }
+
+    fn try_sub(self, rhs: Self) -> Result<Self> {
+        self.checked_sub(rhs)
+            .ok_or_else(|| overflow(self, "-", rhs))
+    }
+
+    fn try_mul(self, rhs: Self) -> Result<Self> {
+        self.checked_mul(rhs)
+            .ok_or_else(|| overflow(self, "*", rhs))
+    }
+}
+
+fn overflow<T: Int>(lhs: T, op: &str, rhs: T) -> Error {
+    Error::overflow(format!(
+        "{} {} {} does not fit in {}",
+        lhs,
+        op,
+        rhs,
+        T::NAME
+    ))
+}
+
+macro_rules! impl_int {
+    ($($t:ident),*) => {$(
+        impl Int for $t {
+            const NAME: &'static str = stringify!($t);
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                $t::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                $t::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                $t::checked_mul(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_int!(i32, i64, i128, u32, u64, u128);
+
+/// Adds up `values`, failing on overflow.
+pub fn sum<T: Int, I: IntoIterator<Item = T>>(values: I) -> Result<T> {
+    values
+        .into_iter()
+        .try_fold(T::from(0), |total, value| total.try_add(value))
+}
+
+/// Converts a length or count, failing if it does not fit.
+pub fn from_usize<T: Int>(count: usize) -> Result<T> {
+    T::try_from(count)
+        .map_err(|_| Error::overflow(format!("{} does not fit in {}", count, T::NAME)))
+}
+
+/// How many of `flags` are `true`, failing if that does not fit.
+pub fn count<T: Int, I: IntoIterator<Item = bool>>(flags: I) -> Result<T> {
+    from_usize(flags.into_iter().filter(|&flag| flag).count())
+}
+
+/// Multiplies `values` together, failing on overflow.
+pub fn product<T: Int, I: IntoIterator<Item = T>>(values: I) -> Result<T> {
+    values
+        .into_iter()
+        .try_fold(T::from(1), |total, value| total.try_mul(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn can_do_checked_arithmetic() {
+        assert_eq!(7, 3i64.try_add(4).unwrap());
+        assert_eq!(-1, 3i32.try_sub(4).unwrap());
+        assert_eq!(12u64, 3u64.try_mul(4).unwrap());
+        assert_eq!(514579i64, product(vec![1721, 299]).unwrap());
+        assert_eq!(0u128, sum(Vec::new()).unwrap());
+        assert_eq!(1i32, product(Vec::new()).unwrap());
+        assert_eq!(2u32, count(vec![true, false, true]).unwrap());
+        assert_eq!(300i64, from_usize(300).unwrap());
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let error = 3u64.try_sub(4).unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::Overflow(_)));
+        assert_eq!("3 - 4 does not fit in u64", error.to_string());
+        assert!(product(vec![979i32, 366, 675, 10]).is_err());
+        assert_eq!(
+            Ok(2418619500i64),
+            product(vec![979i64, 366, 675, 10]).map_err(|e| e.to_string())
+        );
+        assert!(sum(vec![i128::MAX, 1]).is_err());
+        assert_eq!(
+            "4294967296 does not fit in u32",
+            from_usize::<u32>(1 << 32).unwrap_err().to_string()
+        );
+    }
+}
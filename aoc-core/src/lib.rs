@@ -0,0 +1,29 @@
+//! Shared plumbing for the daily puzzle crates: reading the puzzle input,
+//! printing answers and the bits of parsing every other day ends up needing.
+
+// Lets this crate's own tests use `#[aoc]`, which names `::aoc_core`.
+#[cfg(test)]
+extern crate self as aoc_core;
+
+pub mod answers;
+pub mod cli;
+pub mod error;
+pub mod fixtures;
+pub mod input;
+pub mod int;
+pub mod output;
+pub mod parse;
+pub mod registry;
+pub mod repl;
+pub mod rng;
+pub mod solution;
+pub mod trace;
+
+pub use aoc_macros::{aoc, FromPattern};
+pub use error::{exit_on_error, Error, ErrorKind, Result};
+pub use int::Int;
+pub use rng::Rng;
+pub use solution::{Answer, Solution, Solver};
+
+#[doc(hidden)]
+pub use inventory;
@@ -0,0 +1,186 @@
+use crate::solution::Answer;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// How answers are written out: `text` for people, `json` and `tsv` for
+/// anything that wants to read them back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+pub const TSV_HEADER: &str = "day\tpart\tanswer\tparse_ns\tsolve_ns";
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Format, String> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!(
+                "'{}' is not a format, expected text, json or tsv",
+                format
+            )),
+        }
+    }
+}
+
+/// Prints a single answer as `Part <n> <description>: <answer>`.
+pub fn print_answer<T: Display>(part: u8, description: &str, answer: T) {
+    println!("{}", format_answer(part, description, answer));
+}
+
+pub fn format_answer<T: Display>(part: u8, description: &str, answer: T) -> String {
+    format!("Part {} {}: {}", part, description, answer)
+}
+
+/// An explanation indented to sit under its answer in text output.
+pub fn format_explanation(explanation: &str) -> String {
+    explanation
+        .lines()
+        .map(|line| format!("  {}\n", line))
+        .collect()
+}
+
+/// A JSON array with one object per answer. Answers are always strings so a
+/// large number never loses precision in a JavaScript dashboard. An
+/// `explanation` is only there when the answer has one.
+pub fn format_json(answers: &[Answer]) -> String {
+    let objects: Vec<String> = answers
+        .iter()
+        .map(|a| {
+            let explanation = match &a.explanation {
+                Some(explanation) => format!(", \"explanation\": {}", json_string(explanation)),
+                None => String::new(),
+            };
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}{}}}",
+                a.day,
+                a.part,
+                json_string(&a.value),
+                a.parse_time.as_nanos(),
+                a.solve_time.as_nanos(),
+                explanation
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+/// Tab separated answers under a [`TSV_HEADER`] line.
+pub fn format_tsv(answers: &[Answer]) -> String {
+    let mut tsv = format!("{}\n", TSV_HEADER);
+    for a in answers {
+        tsv.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            a.day,
+            a.part,
+            a.value.replace(['\t', '\n'], " "),
+            a.parse_time.as_nanos(),
+            a.solve_time.as_nanos()
+        ));
+    }
+    tsv
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn answers() -> Vec<Answer> {
+        vec![
+            Answer {
+                day: 3,
+                part: 1,
+                value: "7".to_string(),
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(250),
+                explanation: None,
+            },
+            Answer {
+                day: 3,
+                part: 2,
+                value: "336".to_string(),
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(900),
+                explanation: Some("1 * 2\n3".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn can_format_answer() {
+        assert_eq!("Part 1 trees: 7", format_answer(1, "trees", 7));
+        assert_eq!(
+            "Part 2 missing seat id: 640",
+            format_answer(2, "missing seat id", 640)
+        );
+    }
+
+    #[test]
+    fn can_format_explanation() {
+        assert_eq!("  1 * 2\n  3\n", format_explanation("1 * 2\n3"));
+    }
+
+    #[test]
+    fn can_parse_format() {
+        assert_eq!(Ok(Format::Json), "json".parse());
+        assert_eq!(Ok(Format::Tsv), "tsv".parse());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn can_format_json() {
+        assert_eq!(
+            "\
+[
+  {\"day\": 3, \"part\": 1, \"answer\": \"7\", \"parse_ns\": 1500, \"solve_ns\": 250},
+  {\"day\": 3, \"part\": 2, \"answer\": \"336\", \"parse_ns\": 1500, \"solve_ns\": 900, \"explanation\": \"1 * 2\\n3\"}
+]
+",
+            format_json(&answers())
+        );
+        assert_eq!("[]\n", format_json(&[]));
+    }
+
+    #[test]
+    fn can_format_tsv() {
+        assert_eq!(
+            "day\tpart\tanswer\tparse_ns\tsolve_ns\n3\t1\t7\t1500\t250\n3\t2\t336\t1500\t900\n",
+            format_tsv(&answers())
+        );
+    }
+
+    #[test]
+    fn can_escape_json_strings() {
+        assert_eq!(r#""a \"b\"\\c\n""#, json_string("a \"b\"\\c\n"));
+        assert_eq!(r#""\u0001""#, json_string("\u{1}"));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::error::{Error, Result};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// One non-blank line of the input with its 1-based line number. `text` is
+/// the whole line without its `\n` or `\r\n` ending, so error columns and
+/// the text shown under them line up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// The lines of `input` that are not blank or whitespace only.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| Line {
+            number: idx + 1,
+            text,
+        })
+        .filter(|line| !line.text.trim().is_empty())
+}
+
+/// Runs of lines separated by one or more blank lines, like day 4's
+/// passports or day 6's groups of people.
+pub fn groups(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut groups = Vec::new();
+    let mut group: Vec<Line> = Vec::new();
+    for line in lines(input) {
+        if group
+            .last()
+            .is_some_and(|last| last.number + 1 < line.number)
+        {
+            groups.push(group);
+            group = Vec::new();
+        }
+        group.push(line);
+    }
+    if !group.is_empty() {
+        groups.push(group);
+    }
+    groups
+}
+
+/// Parses one number per line, trimming whitespace and skipping blank lines.
+pub fn numbers<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    lines(input)
+        .map(|line| {
+            let value = line.text.trim();
+            value.parse().map_err(|e| {
+                Error::parse(format!("expected a number, found `{}` ({})", value, e))
+                    .at_column(column_of(line.text, value))
+                    .on_line(line.number, line.text)
+            })
+        })
+        .collect()
+}
+
+/// A rectangle of characters, one row per non-blank line with surrounding
+/// whitespace trimmed. Every character has to be one of `allowed`.
+pub fn grid(input: &str, allowed: &str) -> Result<Vec<Vec<char>>> {
+    let mut rows: Vec<Vec<char>> = Vec::new();
+    for line in lines(input) {
+        let trimmed = line.text.trim();
+        let row: Vec<char> = trimmed.chars().collect();
+        if let Some(column) = row.iter().position(|&c| !allowed.contains(c)) {
+            return Err(Error::parse(format!(
+                "unexpected `{}`, expected one of `{}`",
+                row[column], allowed
+            ))
+            .at_column(column_of(line.text, trimmed) + column)
+            .on_line(line.number, line.text));
+        }
+        if let Some(first_row) = rows.first() {
+            if row.len() != first_row.len() {
+                return Err(Error::parse(format!(
+                    "expected a row {} wide like the first, found {}",
+                    first_row.len(),
+                    row.len()
+                ))
+                .on_line(line.number, line.text));
+            }
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// One piece of a `#[pattern("...")]`: text that has to be there, or the
+/// name of a field that runs until the next piece of text.
+pub enum Piece {
+    Text(&'static str),
+    Field(&'static str),
+}
+
+/// Splits `text` along `pieces` into the text of each field, in order. Used by
+/// `#[derive(FromPattern)]`; errors carry the column where matching stopped.
+pub fn split_pattern<'a>(text: &'a str, pieces: &[Piece]) -> Result<Vec<&'a str>> {
+    let mut fields = Vec::new();
+    let mut rest = text;
+    let mut open_field = None;
+    for piece in pieces {
+        match *piece {
+            Piece::Field(name) => open_field = Some(name),
+            Piece::Text(expected) => match open_field.take() {
+                Some(name) => match rest.find(expected) {
+                    Some(end) => {
+                        fields.push(&rest[..end]);
+                        rest = &rest[end + expected.len()..];
+                    }
+                    None => {
+                        return Err(Error::parse(format!(
+                            "expected `{}` after `{}`",
+                            expected, name
+                        ))
+                        .at_column(column_of(text, &rest[rest.len()..])))
+                    }
+                },
+                None => match rest.strip_prefix(expected) {
+                    Some(after) => rest = after,
+                    None => {
+                        return Err(Error::parse(format!(
+                            "expected `{}`, found `{}`",
+                            expected, rest
+                        ))
+                        .at_column(column_of(text, rest)))
+                    }
+                },
+            },
+        }
+    }
+    match open_field {
+        Some(_) => fields.push(rest),
+        None if !rest.is_empty() => {
+            return Err(Error::parse(format!("unexpected `{}` at the end", rest))
+                .at_column(column_of(text, rest)))
+        }
+        None => {}
+    }
+    Ok(fields)
+}
+
+/// Parses the field `name` of a `#[pattern]`, found as `value` in `text`.
+pub fn pattern_field<T>(text: &str, name: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse().map_err(|e| {
+        Error::parse(format!("`{}` cannot be `{}` ({})", name, value, e))
+            .at_column(column_of(text, value))
+    })
+}
+
+/// A `#[pattern]` field that keeps the column it was found at, for checks on
+/// its value that can only be made after parsing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub column: usize,
+}
+
+/// How `#[derive(FromPattern)]` builds a field from its text: with the type's
+/// own `FromStr`, or as a [`Spanned`] value.
+pub trait PatternField: Sized {
+    fn from_field(text: &str, name: &str, value: &str) -> Result<Self>;
+}
+
+impl<T> PatternField for T
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn from_field(text: &str, name: &str, value: &str) -> Result<T> {
+        pattern_field(text, name, value)
+    }
+}
+
+impl<T> PatternField for Spanned<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn from_field(text: &str, name: &str, value: &str) -> Result<Spanned<T>> {
+        Ok(Spanned {
+            value: pattern_field(text, name, value)?,
+            column: column_of(text, value),
+        })
+    }
+}
+
+/// The 1-based character column at which `part`, a slice of `line`, starts.
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset)
+        .map_or(1, |before| before.chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_numbers() {
+        assert_eq!(
+            vec![1721, 979, 366],
+            numbers::<i32>("1721\n979\n366\n").unwrap()
+        );
+        assert_eq!(vec![35, 20], numbers::<i32>(" 35 \r\n20\n\n").unwrap());
+    }
+
+    #[test]
+    fn reports_bad_numbers() {
+        let error = numbers::<i32>("35\n20\n  1x5\n").unwrap_err();
+        assert_eq!(Some(3), error.line);
+        assert_eq!(Some(3), error.column);
+        assert_eq!(Some("  1x5".to_string()), error.text);
+    }
+
+    #[test]
+    fn can_split_lines_and_groups() {
+        let input = "ab\r\nc\r\n\r\n  \r\n\r\nd\ne\n\n";
+        let texts: Vec<&str> = lines(input).map(|line| line.text).collect();
+        assert_eq!(vec!["ab", "c", "d", "e"], texts);
+
+        let groups: Vec<Vec<(usize, &str)>> = groups(input)
+            .iter()
+            .map(|group| group.iter().map(|l| (l.number, l.text)).collect())
+            .collect();
+        assert_eq!(
+            vec![vec![(1, "ab"), (2, "c")], vec![(6, "d"), (7, "e")]],
+            groups
+        );
+        assert!(super::groups("\n \n").is_empty());
+    }
+
+    #[test]
+    fn can_parse_grid() {
+        assert_eq!(
+            vec![vec!['.', '#'], vec!['#', '.']],
+            grid(" .#\r\n#.\n\n", ".#").unwrap()
+        );
+        let error = grid(".#\n.O\n", ".#").unwrap_err();
+        assert_eq!((Some(2), Some(2)), (error.line, error.column));
+        let error = grid(".#\n.#.\n", ".#").unwrap_err();
+        assert_eq!(Some(2), error.line);
+        assert!(grid("", ".#").unwrap().is_empty());
+    }
+
+    #[test]
+    fn can_split_pattern() {
+        use Piece::{Field, Text};
+        let policy = [
+            Field("min"),
+            Text("-"),
+            Field("max"),
+            Text(" "),
+            Field("letter"),
+            Text(": "),
+            Field("password"),
+        ];
+        assert_eq!(
+            vec!["1", "3", "a", "abc: d"],
+            split_pattern("1-3 a: abc: d", &policy).unwrap()
+        );
+        assert_eq!(
+            vec!["", "", "", ""],
+            split_pattern("- : ", &policy).unwrap()
+        );
+
+        let error = |text, pieces| split_pattern(text, pieces).unwrap_err().to_string();
+        assert_eq!(
+            "column 6: expected `: ` after `letter`",
+            error("1-3 a", &policy)
+        );
+        assert_eq!(
+            "column 1: expected `x = `, found `x=1`",
+            error("x=1", &[Text("x = "), Field("x")])
+        );
+        assert_eq!(
+            "column 7: unexpected `!` at the end",
+            error("(1, 2)!", &[Text("("), Field("x"), Text(")")])
+        );
+    }
+
+    #[test]
+    fn can_keep_field_columns() {
+        let text = "01-12 a: abc";
+        let second: Spanned<usize> = PatternField::from_field(text, "second", &text[3..5]).unwrap();
+        assert_eq!(
+            Spanned {
+                value: 12,
+                column: 4
+            },
+            second
+        );
+        let letter: char = PatternField::from_field(text, "letter", &text[6..7]).unwrap();
+        assert_eq!('a', letter);
+        assert!(<Spanned<usize>>::from_field(text, "letter", &text[6..7]).is_err());
+    }
+
+    #[test]
+    fn reports_bad_pattern_fields() {
+        let text = "1-x a: abc";
+        assert_eq!(1, pattern_field::<usize>(text, "min", &text[..1]).unwrap());
+        let error = pattern_field::<usize>(text, "max", &text[2..3]).unwrap_err();
+        assert_eq!(
+            "column 3: `max` cannot be `x` (invalid digit found in string)",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn can_find_column() {
+        let line = "1-3 a: abcde";
+        assert_eq!(1, column_of(line, &line[..1]));
+        assert_eq!(8, column_of(line, &line[7..]));
+    }
+}
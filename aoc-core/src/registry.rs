@@ -0,0 +1,184 @@
+use crate::{Error, Result, Solution, Solver};
+
+/// A day added by `#[aoc(day = N)]`. Only the macro should build these.
+pub struct Registration {
+    pub day: u8,
+    pub solver: fn() -> Box<dyn Solver>,
+}
+
+inventory::collect!(Registration);
+
+/// One part added by `#[aoc(day = N, part = P)]` on a function, wrapped to
+/// parse the input and render the answer. Only the macro should build these.
+pub struct PartRegistration {
+    pub day: u8,
+    pub part: u8,
+    pub solve: fn(&str) -> Result<String>,
+}
+
+inventory::collect!(PartRegistration);
+
+/// A fresh solver for `day`, if a linked crate registered one. A day made of
+/// part functions gets a solver that hands each part the raw input.
+pub fn solver(day: u8) -> Option<Box<dyn Solver>> {
+    let registered = inventory::iter::<Registration>
+        .into_iter()
+        .find(|registration| registration.day == day)
+        .map(|registration| (registration.solver)());
+    registered.or_else(|| {
+        inventory::iter::<PartRegistration>
+            .into_iter()
+            .find(|registration| registration.day == day)
+            .map(|_| Box::new(Parts { day }) as Box<dyn Solver>)
+    })
+}
+
+/// Every registered day, in order. A day registered twice, or with the same
+/// part registered twice, is listed twice so the mistake can be caught. A day
+/// with an `impl Solution` block is listed once for it, whatever part
+/// functions it also has, as that is what [`solver`] runs.
+pub fn days() -> Vec<u8> {
+    let mut days: Vec<u8> = inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| registration.day)
+        .collect();
+    let mut parts: Vec<(u8, u8)> = inventory::iter::<PartRegistration>
+        .into_iter()
+        .map(|registration| (registration.day, registration.part))
+        .filter(|(day, _)| !days.contains(day))
+        .collect();
+    parts.sort_unstable();
+    for (idx, &(day, part)) in parts.iter().enumerate() {
+        if idx == 0 || parts[idx - 1].0 != day || parts[idx - 1] == (day, part) {
+            days.push(day);
+        }
+    }
+    days.sort_unstable();
+    days
+}
+
+/// The solver for a day registered one part function at a time. Each part
+/// parses the input itself, so the whole run counts as the part's time.
+struct Parts {
+    day: u8,
+}
+
+impl Parts {
+    fn solve(&self, input: &str, part: u8) -> Result<String> {
+        match inventory::iter::<PartRegistration>
+            .into_iter()
+            .find(|registration| registration.day == self.day && registration.part == part)
+        {
+            Some(registration) => (registration.solve)(input),
+            None => Err(Error::no_solution(format!("there is no part {} yet", part))),
+        }
+    }
+}
+
+impl Solution for Parts {
+    type Input = String;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part_one(&self, input: &String) -> Result<String> {
+        self.solve(input, 1)
+    }
+
+    fn part_two(&self, input: &String) -> Result<String> {
+        self.solve(input, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aoc, parse};
+
+    #[derive(Default)]
+    struct Answer;
+
+    impl Solution for Answer {
+        type Input = ();
+        type PartOne = u8;
+        type PartTwo = u8;
+
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn parse(&self, _input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part_one(&self, _input: &()) -> Result<u8> {
+            Ok(42)
+        }
+
+        fn part_two(&self, _input: &()) -> Result<u8> {
+            Ok(24)
+        }
+    }
+
+    fn answer() -> Box<dyn Solver> {
+        Box::new(Answer)
+    }
+
+    inventory::submit! {
+        Registration { day: 25, solver: answer }
+    }
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    #[aoc(day = 24, part = 1)]
+    fn count_lines(lines: &[String]) -> Result<usize> {
+        Ok(lines.len())
+    }
+
+    // Day 25 has an `impl Solution` too, which wins.
+    #[aoc(day = 25, part = 1)]
+    fn count_more_lines(lines: &[String]) -> Result<usize> {
+        Ok(lines.len() + 1)
+    }
+
+    #[aoc(day = 24, part = 2, parser = parse::numbers)]
+    fn add_numbers(numbers: &[i64]) -> Result<i64> {
+        Ok(numbers.iter().sum())
+    }
+
+    #[test]
+    fn can_find_registered_days() {
+        assert_eq!(vec![24, 25], days());
+        let solver = solver(25).unwrap();
+        assert_eq!(25, solver.day());
+        assert!(super::solver(23).is_none());
+    }
+
+    #[test]
+    fn prefers_the_solution_of_a_day_registered_both_ways() {
+        assert_eq!(1, days().iter().filter(|&&day| day == 25).count());
+        let solver = solver(25).unwrap();
+        assert_eq!("42", solver.solve("a\nb\n", &[1]).unwrap()[0].value);
+    }
+
+    #[test]
+    fn can_solve_registered_parts() {
+        let solver = solver(24).unwrap();
+        assert_eq!(24, solver.day());
+        let answers = solver.solve("1\n2\n3\n", &[1, 2]).unwrap();
+        let values: Vec<&str> = answers.iter().map(|a| a.value.as_str()).collect();
+        assert_eq!(vec!["3", "6"], values);
+        let error = solver.solve("1\nx\n", &[2]).unwrap_err();
+        assert_eq!(Some(24), error.day);
+        assert_eq!(Some(2), error.line);
+    }
+}
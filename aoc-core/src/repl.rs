@@ -0,0 +1,175 @@
+use crate::{Error, Result, Solution};
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// A day's input, parsed once, answering `repl` commands about it.
+pub trait Explorer {
+    /// The day's own commands as `(usage, description)` pairs.
+    fn commands(&self) -> Vec<(&'static str, &'static str)>;
+
+    /// Runs one command split into words, or `None` if there is no such
+    /// command.
+    fn run(&self, words: &[&str]) -> Option<Result<String>>;
+}
+
+pub(crate) struct Parsed<'a, S: Solution> {
+    pub solution: &'a S,
+    pub input: S::Input,
+}
+
+impl<S: Solution> Explorer for Parsed<'_, S> {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        self.solution.commands()
+    }
+
+    fn run(&self, words: &[&str]) -> Option<Result<String>> {
+        match words {
+            ["part1"] => Some(self.solution.part_one(&self.input).map(|a| a.to_string())),
+            ["part2"] => Some(self.solution.part_two(&self.input).map(|a| a.to_string())),
+            _ => self.solution.explore(&self.input, words),
+        }
+    }
+}
+
+const BUILT_IN: [(&str, &str); 4] = [
+    ("part1", "solve part 1"),
+    ("part2", "solve part 2"),
+    ("help", "list the commands"),
+    ("quit", "leave"),
+];
+
+/// Reads commands from `input` until it ends or says `quit`, printing each
+/// result or error to `output`.
+pub fn run(
+    explorer: &dyn Explorer,
+    prompt: &str,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> io::Result<()> {
+    loop {
+        write!(output, "{}", prompt)?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["quit"] | ["exit"] => return Ok(()),
+            ["help"] => {
+                let commands: Vec<_> = explorer
+                    .commands()
+                    .into_iter()
+                    .chain(BUILT_IN.iter().cloned())
+                    .collect();
+                let width = commands.iter().map(|(usage, _)| usage.len()).max();
+                for (usage, description) in commands.iter() {
+                    writeln!(
+                        output,
+                        "{:<width$}  {}",
+                        usage,
+                        description,
+                        width = width.unwrap_or(0)
+                    )?;
+                }
+            }
+            _ => match explorer.run(&words) {
+                Some(Ok(result)) => writeln!(output, "{}", result)?,
+                Some(Err(e)) => writeln!(output, "error: {}", e)?,
+                None => writeln!(output, "unknown command `{}`, try `help`", words[0])?,
+            },
+        }
+    }
+}
+
+/// The word at `idx` read as a `T`, for commands like `slope 3 1`.
+pub fn argument<T: FromStr>(words: &[&str], idx: usize, what: &str) -> Result<T> {
+    match words.get(idx) {
+        Some(word) => word
+            .parse()
+            .map_err(|_| Error::parse(format!("`{}` is not a valid {}", word, what))),
+        None => Err(Error::parse(format!(
+            "`{}` needs a {}",
+            words[..idx].join(" "),
+            what
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solver;
+
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Input = Vec<i64>;
+        type PartOne = i64;
+        type PartTwo = i64;
+
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<i64>> {
+            crate::parse::numbers(input)
+        }
+
+        fn part_one(&self, numbers: &Vec<i64>) -> Result<i64> {
+            Ok(numbers.iter().sum())
+        }
+
+        fn part_two(&self, _numbers: &Vec<i64>) -> Result<i64> {
+            Err(Error::no_solution("no part two"))
+        }
+
+        fn commands(&self) -> Vec<(&'static str, &'static str)> {
+            vec![("at <n>", "show the nth number")]
+        }
+
+        fn explore(&self, numbers: &Vec<i64>, words: &[&str]) -> Option<Result<String>> {
+            match words[0] {
+                "at" => Some(argument(words, 1, "position").and_then(|n: usize| {
+                    numbers
+                        .get(n)
+                        .map(|v| v.to_string())
+                        .ok_or_else(|| Error::parse(format!("there is no number {}", n)))
+                })),
+                _ => None,
+            }
+        }
+    }
+
+    fn session(commands: &str) -> String {
+        let explorer = Numbers.explorer("3\n4\n5\n").unwrap();
+        let mut output = Vec::new();
+        run(&*explorer, "> ", &mut commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn can_run_commands() {
+        assert_eq!(
+            "> 12\n> 4\n> > error: no part two\n> ",
+            session("part1\nat 1\n\npart2\nquit\npart1\n")
+        );
+        assert_eq!(
+            "> error: `at` needs a position\n> error: `x` is not a valid position\n> \n",
+            session("at\nat x\n")
+        );
+        assert_eq!(
+            "> unknown command `walk`, try `help`\n> \n",
+            session("walk")
+        );
+    }
+
+    #[test]
+    fn can_list_commands() {
+        let help = session("help\n");
+        assert!(help.contains("at <n>  show the nth number\n"), "{}", help);
+        assert!(help.contains("\npart1   solve part 1\n"), "{}", help);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::error::{Error, Result};
+use crate::repl::{Explorer, Parsed};
+use crate::rng::Rng;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// One day of the puzzle: a parse step shared by both parts, then the parts
+/// themselves working off the parsed input.
+pub trait Solution {
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part_one(&self, input: &Self::Input) -> Result<Self::PartOne>;
+    fn part_two(&self, input: &Self::Input) -> Result<Self::PartTwo>;
+
+    /// Applies a `name: value` setting from a sample fixture, such as day 9's
+    /// shorter preamble. Days without settings reject every name.
+    fn configure(&mut self, name: &str, _value: &str) -> Result<()> {
+        Err(Error::parse(format!("unknown setting `{}`", name)))
+    }
+
+    /// A random but valid input of roughly `size` lines or records, if the
+    /// day has a generator.
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// The day's own `repl` commands as `(usage, description)` pairs.
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        Vec::new()
+    }
+
+    /// Runs one of [`commands`](Solution::commands), split into words,
+    /// against the parsed input. `None` if the day has no such command.
+    fn explore(&self, _input: &Self::Input, _words: &[&str]) -> Option<Result<String>> {
+        None
+    }
+
+    /// The evidence behind the answer to `part`, such as the entries that
+    /// add up to it. `None` if the day has nothing to show.
+    fn explain(&self, _input: &Self::Input, _part: u8) -> Option<Result<String>> {
+        None
+    }
+}
+
+/// An answer to one part, already rendered for display, with how long the
+/// shared parse step and the part itself took, and the explanation when one
+/// was asked for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub value: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub explanation: Option<String>,
+}
+
+/// The object-safe face of [`Solution`], so runners can hold every day in
+/// one collection without knowing their input or answer types.
+pub trait Solver {
+    fn day(&self) -> u8;
+
+    /// Parses `input` once and solves the requested `parts` (1 and/or 2) in
+    /// order, skipping anything that is not a part number. Errors are tagged
+    /// with the day.
+    fn solve(&self, input: &str, parts: &[u8]) -> Result<Vec<Answer>>;
+
+    /// Like [`solve`](Solver::solve), with each answer's explanation filled
+    /// in after it is timed.
+    fn explain(&self, input: &str, parts: &[u8]) -> Result<Vec<Answer>>;
+
+    fn configure(&mut self, name: &str, value: &str) -> Result<()>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+
+    /// Parses `input` once for a `repl` session.
+    fn explorer<'a>(&'a self, input: &str) -> Result<Box<dyn Explorer + 'a>>;
+}
+
+impl<S: Solution> Solver for S {
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn solve(&self, input: &str, parts: &[u8]) -> Result<Vec<Answer>> {
+        solve_parts(self, input, parts, false)
+    }
+
+    fn explain(&self, input: &str, parts: &[u8]) -> Result<Vec<Answer>> {
+        solve_parts(self, input, parts, true)
+    }
+
+    fn configure(&mut self, name: &str, value: &str) -> Result<()> {
+        let day = Solution::day(self);
+        Solution::configure(self, name, value).map_err(|e| e.in_day(day))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Solution::generate(self, rng, size)
+    }
+
+    fn explorer<'a>(&'a self, input: &str) -> Result<Box<dyn Explorer + 'a>> {
+        let day = Solution::day(self);
+        let input = self.parse(input).map_err(|e| e.in_day(day))?;
+        Ok(Box::new(Parsed {
+            solution: self,
+            input,
+        }))
+    }
+}
+
+fn solve_parts<S: Solution>(
+    solution: &S,
+    input: &str,
+    parts: &[u8],
+    explain: bool,
+) -> Result<Vec<Answer>> {
+    let day = solution.day();
+    let start = Instant::now();
+    let parsed = solution.parse(input).map_err(|e| e.in_day(day))?;
+    let parse_time = start.elapsed();
+
+    let mut answers = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let value = match part {
+            1 => solution.part_one(&parsed).map(|a| a.to_string()),
+            2 => solution.part_two(&parsed).map(|a| a.to_string()),
+            _ => continue,
+        };
+        let solve_time = start.elapsed();
+        let value = value.map_err(|e| e.in_day(day))?;
+        let explanation = if explain {
+            solution
+                .explain(&parsed, part)
+                .transpose()
+                .map_err(|e| e.in_day(day))?
+        } else {
+            None
+        };
+        answers.push(Answer {
+            day,
+            part,
+            value,
+            parse_time,
+            solve_time,
+            explanation,
+        });
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        type Input = Vec<String>;
+        type PartOne = usize;
+        type PartTwo = String;
+
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<String>> {
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part_one(&self, lines: &Vec<String>) -> Result<usize> {
+            Ok(lines.len())
+        }
+
+        fn part_two(&self, lines: &Vec<String>) -> Result<String> {
+            if lines.is_empty() {
+                return Err(Error::no_solution("nothing to join"));
+            }
+            Ok(lines.join("+"))
+        }
+    }
+
+    #[test]
+    fn can_solve_through_trait_object() {
+        let solver: Box<dyn Solver> = Box::new(LineCount);
+        assert_eq!(25, solver.day());
+
+        let answers = solver.solve("a\nb", &[2, 3, 1]).unwrap();
+        let parts: Vec<(u8, u8, &str)> = answers
+            .iter()
+            .map(|a| (a.day, a.part, a.value.as_str()))
+            .collect();
+        assert_eq!(vec![(25, 2, "a+b"), (25, 1, "2")], parts);
+        assert_eq!(answers[0].parse_time, answers[1].parse_time);
+    }
+
+    #[test]
+    fn tags_errors_with_the_day() {
+        let error = LineCount.solve("", &[1, 2]).unwrap_err();
+        assert_eq!(Some(25), error.day);
+        let error = Solver::configure(&mut LineCount, "preamble", "5").unwrap_err();
+        assert_eq!(Some(25), error.day);
+    }
+
+    #[derive(Default)]
+    struct Explained {
+        calls: Cell<usize>,
+    }
+
+    impl Solution for Explained {
+        type Input = ();
+        type PartOne = u8;
+        type PartTwo = u8;
+
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn parse(&self, _input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part_one(&self, _input: &()) -> Result<u8> {
+            Ok(1)
+        }
+
+        fn part_two(&self, _input: &()) -> Result<u8> {
+            Ok(2)
+        }
+
+        fn explain(&self, _input: &(), part: u8) -> Option<Result<String>> {
+            self.calls.set(self.calls.get() + 1);
+            Some(Ok(format!("because {}", part)))
+        }
+    }
+
+    #[test]
+    fn only_explains_when_asked() {
+        let explained = Explained::default();
+        let answers = Solver::solve(&explained, "", &[1, 2]).unwrap();
+        assert_eq!(0, explained.calls.get());
+        assert_eq!(None, answers[1].explanation);
+
+        let answers = Solver::explain(&explained, "", &[1, 2]).unwrap();
+        assert_eq!(2, explained.calls.get());
+        assert_eq!(Some("because 2"), answers[1].explanation.as_deref());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::output::json_string;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+/// How much of a solver's working to show: `-v` shows `Info`, `-vv` adds
+/// `Debug`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info = 1,
+    Debug = 2,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TraceFormat {
+    /// `debug aoc03: step x=3 y=1 tree=false`
+    Text,
+    /// `{"level":"debug","target":"aoc03","event":"step","x":3,"y":1,"tree":false}`
+    Json,
+}
+
+/// A field of an event. Numbers and booleans stay unquoted in JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(String),
+    Bool(bool),
+    Text(String),
+}
+
+impl Value {
+    /// For numbers whose type is only known to be `Display`, like an `Int`.
+    pub fn number<T: Display>(number: T) -> Value {
+        Value::Number(number.to_string())
+    }
+}
+
+macro_rules! number_values {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(number: $t) -> Value {
+                    Value::number(number)
+                }
+            }
+        )*
+    };
+}
+
+number_values!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Value {
+        Value::Bool(value)
+    }
+}
+
+impl From<char> for Value {
+    fn from(value: char) -> Value {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Value {
+        Value::Text(value)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(number) => write!(f, "{}", number),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Records `event` with `name = value` fields if `level` is switched on, e.g.
+/// `trace!(Debug, "step", x = x, y = y)`. The fields are only evaluated when
+/// the event is recorded, so a trace in a hot loop costs one atomic load.
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $event:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::emit(
+                $crate::trace::Level::$level,
+                module_path!(),
+                $event,
+                &[$((stringify!($name), $crate::trace::Value::from($value))),*],
+            );
+        }
+    };
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+type Sink = (TraceFormat, Box<dyn Write + Send>);
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+/// Sends every event up to `level` to `sink`, one per line.
+pub fn start(level: Level, format: TraceFormat, sink: Box<dyn Write + Send>) {
+    *SINK.lock().unwrap() = Some((format, sink));
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Switches tracing off again and flushes what was recorded.
+pub fn stop() -> io::Result<()> {
+    LEVEL.store(0, Ordering::Relaxed);
+    match SINK.lock().unwrap().take() {
+        Some((_, mut sink)) => sink.flush(),
+        None => Ok(()),
+    }
+}
+
+/// Starts tracing as the `-v`, `-vv` and `--trace-json <path>` options ask:
+/// as text on stderr, or as JSON lines in the file, where no `-v` means
+/// everything.
+pub fn start_from_args(verbosity: u8, json: Option<&Path>) -> io::Result<()> {
+    let level = match verbosity {
+        0 if json.is_none() => return Ok(()),
+        1 => Level::Info,
+        _ => Level::Debug,
+    };
+    match json {
+        Some(path) => start(
+            level,
+            TraceFormat::Json,
+            Box::new(BufWriter::new(File::create(path)?)),
+        ),
+        None => start(level, TraceFormat::Text, Box::new(io::stderr())),
+    }
+    Ok(())
+}
+
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+#[doc(hidden)]
+pub fn emit(level: Level, target: &str, event: &str, fields: &[(&str, Value)]) {
+    if let Some((format, sink)) = SINK.lock().unwrap().as_mut() {
+        let line = format_event(*format, level, target, event, fields);
+        // A trace that cannot be written is not worth failing the answer for.
+        let _ = writeln!(sink, "{}", line);
+    }
+}
+
+pub fn format_event(
+    format: TraceFormat,
+    level: Level,
+    target: &str,
+    event: &str,
+    fields: &[(&str, Value)],
+) -> String {
+    match format {
+        TraceFormat::Text => {
+            let mut line = format!("{} {}: {}", level.name(), target, event);
+            for (name, value) in fields {
+                line.push_str(&format!(" {}={}", name, value));
+            }
+            line
+        }
+        TraceFormat::Json => {
+            let mut members = vec![
+                format!("\"level\":\"{}\"", level.name()),
+                format!("\"target\":{}", json_string(target)),
+                format!("\"event\":{}", json_string(event)),
+            ];
+            for (name, value) in fields {
+                let value = match value {
+                    Value::Number(number) => number.clone(),
+                    Value::Bool(value) => value.to_string(),
+                    Value::Text(text) => json_string(text),
+                };
+                members.push(format!("{}:{}", json_string(name), value));
+            }
+            format!("{{{}}}", members.join(","))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn can_format_events() {
+        let fields = [
+            ("x", Value::from(3usize)),
+            ("tree", Value::from(true)),
+            ("pass", Value::from("FB\"")),
+        ];
+        assert_eq!(
+            "debug aoc03: step x=3 tree=true pass=FB\"",
+            format_event(TraceFormat::Text, Level::Debug, "aoc03", "step", &fields)
+        );
+        assert_eq!(
+            r#"{"level":"info","target":"aoc03","event":"step","x":3,"tree":true,"pass":"FB\""}"#,
+            format_event(TraceFormat::Json, Level::Info, "aoc03", "step", &fields)
+        );
+    }
+
+    #[test]
+    fn records_only_enabled_levels() {
+        let buffer = Shared(Arc::new(Mutex::new(Vec::new())));
+        let mut evaluated = 0;
+        let mut count = || {
+            evaluated += 1;
+            evaluated
+        };
+        trace!(Info, "before", n = count());
+
+        start(Level::Info, TraceFormat::Text, Box::new(buffer.clone()));
+        trace!(Info, "kept", n = count());
+        trace!(Debug, "skipped", n = count());
+        stop().unwrap();
+        trace!(Info, "after", n = count());
+
+        let recorded = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!("info aoc_core::trace::tests: kept n=1\n", recorded);
+        assert_eq!(1, evaluated);
+    }
+}
@@ -0,0 +1,424 @@
+//! The `#[aoc(day = N)]` attribute and `#[derive(FromPattern)]`, re-exported
+//! from `aoc_core`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::Parser;
+use syn::{
+    parse_quote, Data, DataStruct, DeriveInput, Fields, Item, ItemFn, ItemImpl, LitInt, LitStr,
+    Path,
+};
+
+/// Goes on a day's `impl Solution for DayNN` block. Fills in `day()` and adds
+/// the day to `aoc_core::registry`, built with `Default::default()`, so every
+/// runner linking the crate finds it without being told.
+///
+/// Also goes on a free function solving one part, as
+/// `#[aoc(day = 5, part = 2)]`. The function takes the parsed input by
+/// reference and returns `Result` of anything `Display`; the input comes from
+/// `parse(&str) -> Result<_>` in scope, or the function named by `parser = ...`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(args: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+    let mut day: Option<u8> = None;
+    let mut part: Option<u8> = None;
+    let mut parser: Option<Path> = None;
+    let arguments = syn::meta::parser(|meta| {
+        if meta.path.is_ident("day") {
+            let value: LitInt = meta.value()?.parse()?;
+            match value.base10_parse()? {
+                n @ 1..=25 => day = Some(n),
+                _ => return Err(syn::Error::new(value.span(), "days go from 1 to 25")),
+            }
+            Ok(())
+        } else if meta.path.is_ident("part") {
+            let value: LitInt = meta.value()?.parse()?;
+            match value.base10_parse()? {
+                n @ 1..=2 => part = Some(n),
+                _ => return Err(syn::Error::new(value.span(), "parts are 1 or 2")),
+            }
+            Ok(())
+        } else if meta.path.is_ident("parser") {
+            parser = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `day = N`, `part = N` or `parser = ...`"))
+        }
+    });
+    arguments.parse2(args.clone())?;
+    let day = day.ok_or_else(|| syn::Error::new_spanned(&args, "expected `day = N`"))?;
+
+    match syn::parse2(item)? {
+        Item::Impl(solution) => match (part, parser) {
+            (None, None) => expand_solution(day, solution),
+            _ => Err(syn::Error::new_spanned(
+                &args,
+                "`part` and `parser` go on part functions, not `impl Solution` blocks",
+            )),
+        },
+        Item::Fn(function) => match part {
+            Some(part) => {
+                let parser = parser.unwrap_or_else(|| parse_quote!(parse));
+                Ok(expand_part(day, part, parser, function))
+            }
+            None => Err(syn::Error::new_spanned(
+                &function.sig.ident,
+                "a part function needs `part = 1` or `part = 2`",
+            )),
+        },
+        item => Err(syn::Error::new_spanned(
+            item,
+            "#[aoc] goes on an `impl Solution for ...` block or a part function",
+        )),
+    }
+}
+
+fn expand_solution(day: u8, mut solution: ItemImpl) -> syn::Result<TokenStream2> {
+    if solution.trait_.is_none() {
+        return Err(syn::Error::new_spanned(
+            &solution.self_ty,
+            "#[aoc] goes on an `impl Solution for ...` block",
+        ));
+    }
+    solution.items.push(parse_quote! {
+        fn day(&self) -> u8 {
+            #day
+        }
+    });
+    let self_ty = &solution.self_ty;
+
+    Ok(quote! {
+        #solution
+
+        const _: () = {
+            fn solver() -> ::std::boxed::Box<dyn ::aoc_core::Solver> {
+                ::std::boxed::Box::new(<#self_ty as ::std::default::Default>::default())
+            }
+            ::aoc_core::inventory::submit! {
+                ::aoc_core::registry::Registration { day: #day, solver }
+            }
+        };
+    })
+}
+
+fn expand_part(day: u8, part: u8, parser: Path, function: ItemFn) -> TokenStream2 {
+    let name = &function.sig.ident;
+
+    quote! {
+        #function
+
+        const _: () = {
+            fn solve(input: &str) -> ::aoc_core::Result<::std::string::String> {
+                let parsed = #parser(input)?;
+                #name(&parsed).map(|answer| answer.to_string())
+            }
+            ::aoc_core::inventory::submit! {
+                ::aoc_core::registry::PartRegistration { day: #day, part: #part, solve }
+            }
+        };
+    }
+}
+
+/// Implements `FromStr` for a struct with named fields from a pattern such as
+/// `#[pattern("{min}-{max} {letter}: {password}")]`. Each field runs until the
+/// text that follows it and is parsed with its own `FromStr`; errors say which
+/// field or piece of text did not match and at which column. A field declared
+/// as `aoc_core::parse::Spanned<T>` also keeps the column it started at.
+#[proc_macro_derive(FromPattern, attributes(pattern))]
+pub fn from_pattern(item: TokenStream) -> TokenStream {
+    derive_from_pattern(item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_from_pattern(item: TokenStream2) -> syn::Result<TokenStream2> {
+    let input: DeriveInput = syn::parse2(item)?;
+    let fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[derive(FromPattern)] needs a struct with named fields",
+            ))
+        }
+    };
+    let pattern: LitStr = match input.attrs.iter().find(|a| a.path().is_ident("pattern")) {
+        Some(attribute) => attribute.parse_args()?,
+        None => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "expected a `#[pattern(\"...\")]` to parse with",
+            ))
+        }
+    };
+    let segments =
+        split_segments(&pattern.value()).map_err(|e| syn::Error::new(pattern.span(), e))?;
+
+    let mut names: Vec<&str> = Vec::new();
+    for segment in segments.iter() {
+        if let Segment::Field(name) = segment {
+            if names.contains(&name.as_str()) {
+                let message = format!("`{}` is in the pattern twice", name);
+                return Err(syn::Error::new(pattern.span(), message));
+            }
+            names.push(name);
+        }
+    }
+    let mut idents = Vec::new();
+    let mut types = Vec::new();
+    for name in names.iter() {
+        match fields
+            .iter()
+            .find(|f| f.ident.as_ref().is_some_and(|i| i == name))
+        {
+            Some(field) => {
+                idents.push(&field.ident);
+                types.push(&field.ty);
+            }
+            None => {
+                let message = format!("there is no field `{}`", name);
+                return Err(syn::Error::new(pattern.span(), message));
+            }
+        }
+    }
+    if let Some(field) = fields.iter().find(|f| !idents.contains(&&f.ident)) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "every field has to be in the pattern",
+        ));
+    }
+
+    let pieces = segments.iter().map(|segment| match segment {
+        Segment::Text(text) => quote!(::aoc_core::parse::Piece::Text(#text)),
+        Segment::Field(name) => quote!(::aoc_core::parse::Piece::Field(#name)),
+    });
+    let positions = 0..names.len();
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #type_generics #where_clause {
+            type Err = ::aoc_core::Error;
+
+            fn from_str(text: &str) -> ::aoc_core::Result<Self> {
+                let fields = ::aoc_core::parse::split_pattern(text, &[#(#pieces),*])?;
+                Ok(#ident {
+                    #(#idents: <#types as ::aoc_core::parse::PatternField>::from_field(
+                        text,
+                        #names,
+                        fields[#positions],
+                    )?,)*
+                })
+            }
+        }
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Text(String),
+    Field(String),
+}
+
+fn split_segments(pattern: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = pattern;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('{') {
+            let end = after.find('}').ok_or("unclosed `{` in the pattern")?;
+            let name = &after[..end];
+            if name.is_empty() || name.contains('{') {
+                return Err("expected a field name between `{` and `}`".to_string());
+            }
+            // Nothing would say where the first field stops.
+            if let Some(Segment::Field(previous)) = segments.last() {
+                return Err(format!(
+                    "`{}` and `{}` need some text between them",
+                    previous, name
+                ));
+            }
+            segments.push(Segment::Field(name.to_string()));
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find('{').unwrap_or(rest.len());
+            if rest[..end].contains('}') {
+                return Err("unmatched `}` in the pattern".to_string());
+            }
+            segments.push(Segment::Text(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(args: &str, item: &str) -> syn::Result<String> {
+        expand(args.parse().unwrap(), item.parse().unwrap()).map(|t| t.to_string())
+    }
+
+    #[test]
+    fn fills_in_day_and_registers() {
+        let expanded = expand_str("day = 5", "impl Solution for Day05 {}").unwrap();
+        assert!(
+            expanded.contains("fn day (& self) -> u8 { 5u8 }"),
+            "{}",
+            expanded
+        );
+        assert!(
+            expanded.contains("Registration { day : 5u8 , solver }"),
+            "{}",
+            expanded
+        );
+        assert!(expanded.contains("< Day05 as :: std :: default :: Default >"));
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        let item = "impl Solution for Day05 {}";
+        for (args, message) in [
+            ("", "expected `day = N`"),
+            ("part = 2", "expected `day = N`"),
+            ("day = 26", "days go from 1 to 25"),
+            ("day = x", "expected integer literal"),
+            (
+                "day = 5, year = 2020",
+                "expected `day = N`, `part = N` or `parser = ...`",
+            ),
+            (
+                "day = 5, part = 2",
+                "`part` and `parser` go on part functions, not `impl Solution` blocks",
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(*message, expand_str(args, item).unwrap_err().to_string());
+        }
+        assert_eq!(
+            "#[aoc] goes on an `impl Solution for ...` block",
+            expand_str("day = 5", "impl Day05 {}")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn registers_part_functions() {
+        let function = "fn do_part_two(seats: &[i32]) -> Result<i32> { Ok(seats[0]) }";
+        let expanded = expand_str("day = 5, part = 2", function).unwrap();
+        assert!(expanded.contains("fn do_part_two (seats"), "{}", expanded);
+        assert!(
+            expanded.contains("let parsed = parse (input) ? ; do_part_two (& parsed)"),
+            "{}",
+            expanded
+        );
+        assert!(
+            expanded.contains("PartRegistration { day : 5u8 , part : 2u8 , solve }"),
+            "{}",
+            expanded
+        );
+        let expanded = expand_str("day = 5, part = 1, parser = seats::read", function).unwrap();
+        assert!(
+            expanded.contains("let parsed = seats :: read (input) ?"),
+            "{}",
+            expanded
+        );
+
+        for (args, message) in [
+            ("day = 5", "a part function needs `part = 1` or `part = 2`"),
+            ("day = 5, part = 3", "parts are 1 or 2"),
+            ("part = 2", "expected `day = N`"),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                *message,
+                expand_str(args, function).unwrap_err().to_string()
+            );
+        }
+        assert_eq!(
+            "#[aoc] goes on an `impl Solution for ...` block or a part function",
+            expand_str("day = 5", "struct Day05;")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn can_split_patterns() {
+        use Segment::{Field, Text};
+        assert_eq!(
+            Ok(vec![
+                Field("min".to_string()),
+                Text("-".to_string()),
+                Field("max".to_string()),
+                Text(": ".to_string()),
+            ]),
+            split_segments("{min}-{max}: ")
+        );
+        assert_eq!(
+            Err("`a` and `b` need some text between them".to_string()),
+            split_segments("{a}{b}")
+        );
+        assert!(split_segments("{a").is_err());
+        assert!(split_segments("{}").is_err());
+        assert!(split_segments("a}").is_err());
+    }
+
+    #[test]
+    fn derives_from_str() {
+        let derive = |item: &str| derive_from_pattern(item.parse().unwrap()).map(|t| t.to_string());
+        let expanded = derive("#[pattern(\"{x},{y}\")] struct Point { y: i32, x: i32 }").unwrap();
+        assert!(
+            expanded
+                .contains("Piece :: Field (\"x\") , :: aoc_core :: parse :: Piece :: Text (\",\")"),
+            "{}",
+            expanded
+        );
+        assert!(
+            expanded.contains(
+                "x : < i32 as :: aoc_core :: parse :: PatternField > :: from_field (text , \"x\" , fields [0usize] ,) ?"
+            ),
+            "{}",
+            expanded
+        );
+
+        for (item, message) in [
+            (
+                "struct Point { x: i32 }",
+                "expected a `#[pattern(\"...\")]` to parse with",
+            ),
+            (
+                "#[pattern(\"{x},{z}\")] struct Point { x: i32 }",
+                "there is no field `z`",
+            ),
+            (
+                "#[pattern(\"{x},{x}\")] struct Point { x: i32 }",
+                "`x` is in the pattern twice",
+            ),
+            (
+                "#[pattern(\"{x}\")] struct Point { x: i32, y: i32 }",
+                "every field has to be in the pattern",
+            ),
+            (
+                "#[pattern(\"{0}\")] struct Point(i32);",
+                "#[derive(FromPattern)] needs a struct with named fields",
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(*message, derive(item).unwrap_err().to_string());
+        }
+    }
+}
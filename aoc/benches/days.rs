@@ -0,0 +1,54 @@
+use aoc_core::input::InputConfig;
+use aoc_core::Solution;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// The day's committed input and its parse, as long as both parts solve it.
+fn prepare<S: Solution>(solution: &S) -> Result<(String, S::Input), String> {
+    let input = InputConfig::default()
+        .read_file(solution.day(), None)
+        .map_err(|e| e.to_string())?;
+    let parsed = solution.parse(&input).map_err(|e| e.to_string())?;
+    solution.part_one(&parsed).map_err(|e| e.to_string())?;
+    solution.part_two(&parsed).map_err(|e| e.to_string())?;
+    Ok((input, parsed))
+}
+
+/// Benches parsing and each part separately on the day's committed input, as
+/// `dayNN/parse`, `dayNN/part1` and `dayNN/part2`. A day without input, or
+/// one that cannot solve it yet, such as one fresh from `new-day`, is skipped
+/// with a note.
+fn bench_day<S: Solution>(c: &mut Criterion, solution: S) {
+    let day = solution.day();
+    let (input, parsed) = match prepare(&solution) {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            eprintln!("skipping day {}: {}", day, e);
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group(format!("day{:02}", day));
+    group.bench_function("parse", |b| {
+        b.iter(|| solution.parse(black_box(&input)).unwrap())
+    });
+    group.bench_function("part1", |b| {
+        b.iter(|| solution.part_one(black_box(&parsed)).unwrap())
+    });
+    group.bench_function("part2", |b| {
+        b.iter(|| solution.part_two(black_box(&parsed)).unwrap())
+    });
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day(c, aoc01::Day01);
+    bench_day(c, aoc02::Day02);
+    bench_day(c, aoc03::Day03);
+    bench_day(c, aoc04::Day04);
+    bench_day(c, aoc05::Day05);
+    bench_day(c, aoc06::Day06);
+    bench_day(c, aoc09::Day09::default());
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,399 @@
+use aoc_core::cli::{parse_format, parse_part};
+use aoc_core::output::Format;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: aoc run <day> [--part 1|2] [--input <path>] [--format text|json|tsv] [--explain]
+               [--timeout <time>] [-v|-vv] [--trace-json <path>]
+       aoc all [--format text|json|tsv] [--jobs <n>] [--timeout <time>]
+       aoc verify [--accept] [--timeout <time>]
+       aoc new-day <day>
+       aoc fetch <day>
+       aoc submit <day> <part>
+       aoc generate <day> [--size <n>] [--seed <n>]
+       aoc watch <day>
+       aoc repl <day> [--input <path>]";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        day: u8,
+        part: Option<u8>,
+        input: Option<PathBuf>,
+        format: Format,
+        explain: bool,
+        timeout: Option<Duration>,
+        verbosity: u8,
+        trace_json: Option<PathBuf>,
+    },
+    All {
+        format: Format,
+        jobs: Option<usize>,
+        timeout: Option<Duration>,
+    },
+    Verify {
+        accept: bool,
+        timeout: Option<Duration>,
+    },
+    NewDay {
+        day: u8,
+    },
+    Fetch {
+        day: u8,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+    },
+    Generate {
+        day: u8,
+        size: usize,
+        seed: u64,
+    },
+    Watch {
+        day: u8,
+    },
+    Repl {
+        day: u8,
+        input: Option<PathBuf>,
+    },
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => parse_run(args),
+        Some("all") => parse_all(args),
+        Some("verify") => parse_verify(args),
+        Some("new-day") => Ok(Command::NewDay {
+            day: parse_only_day(args)?,
+        }),
+        Some("fetch") => Ok(Command::Fetch {
+            day: parse_only_day(args)?,
+        }),
+        Some("submit") => match (args.next(), args.next(), args.next()) {
+            (Some(day), Some(part), None) => Ok(Command::Submit {
+                day: parse_day(&day)?,
+                part: parse_part(Some(part))?,
+            }),
+            (_, _, Some(extra)) => Err(format!("unexpected argument '{}'", extra)),
+            _ => Err("submit needs a day and a part".to_string()),
+        },
+        Some("generate") => parse_generate(args),
+        Some("watch") => Ok(Command::Watch {
+            day: parse_only_day(args)?,
+        }),
+        Some("repl") => match (
+            args.next(),
+            args.next().as_deref(),
+            args.next(),
+            args.next(),
+        ) {
+            (Some(day), None, _, _) => Ok(Command::Repl {
+                day: parse_day(&day)?,
+                input: None,
+            }),
+            (Some(day), Some("--input") | Some("-i"), Some(path), None) => Ok(Command::Repl {
+                day: parse_day(&day)?,
+                input: Some(PathBuf::from(path)),
+            }),
+            (None, _, _, _) => Err("missing day".to_string()),
+            _ => Err("repl takes a day and optionally --input <path>".to_string()),
+        },
+        Some(other) => Err(format!("unknown command '{}'", other)),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_run<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let day = match args.next() {
+        Some(day) => parse_day(&day)?,
+        None => return Err("missing day".to_string()),
+    };
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut explain = false;
+    let mut timeout = None;
+    let mut verbosity = 0;
+    let mut trace_json = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_part(args.next())?),
+            "--input" | "-i" => match args.next() {
+                Some(path) => input = Some(PathBuf::from(path)),
+                None => return Err("--input needs a path".to_string()),
+            },
+            "--format" | "-f" => format = parse_format(args.next())?,
+            "--explain" | "-e" => explain = true,
+            "--timeout" | "-t" => timeout = Some(parse_duration("--timeout", args.next())?),
+            "--verbose" | "-v" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            "--trace-json" => match args.next() {
+                Some(path) => trace_json = Some(PathBuf::from(path)),
+                None => return Err("--trace-json needs a path".to_string()),
+            },
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::Run {
+        day,
+        part,
+        input,
+        format,
+        explain,
+        timeout,
+        verbosity,
+        trace_json,
+    })
+}
+
+fn parse_all<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut format = Format::Text;
+    let mut jobs = None;
+    let mut timeout = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" | "-f" => format = parse_format(args.next())?,
+            "--jobs" | "-j" => match parse_number("--jobs", args.next())? {
+                0 => return Err("--jobs needs at least 1".to_string()),
+                n => jobs = Some(n),
+            },
+            "--timeout" | "-t" => timeout = Some(parse_duration("--timeout", args.next())?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::All {
+        format,
+        jobs,
+        timeout,
+    })
+}
+
+fn parse_verify<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut accept = false;
+    let mut timeout = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--accept" => accept = true,
+            "--timeout" | "-t" => timeout = Some(parse_duration("--timeout", args.next())?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::Verify { accept, timeout })
+}
+
+fn parse_generate<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let day = match args.next() {
+        Some(day) => parse_day(&day)?,
+        None => return Err("missing day".to_string()),
+    };
+    let mut size = 1000;
+    let mut seed = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" | "-n" => size = parse_number("--size", args.next())?,
+            "--seed" | "-s" => seed = parse_number("--seed", args.next())?,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::Generate { day, size, seed })
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("{} needs a number, not '{}'", flag, value)),
+        None => Err(format!("{} needs a number", flag)),
+    }
+}
+
+/// Seconds, or a number ending in `s` or `ms`, such as `10`, `1.5s` or `500ms`.
+fn parse_duration(flag: &str, value: Option<String>) -> Result<Duration, String> {
+    let value = value.ok_or(format!("{} needs a time such as 10s or 500ms", flag))?;
+    let seconds = match value.strip_suffix("ms") {
+        Some(millis) => millis.parse::<f64>().map(|ms| ms / 1000.0),
+        None => value.strip_suffix('s').unwrap_or(&value).parse::<f64>(),
+    };
+    match seconds.map(Duration::try_from_secs_f64) {
+        Ok(Ok(duration)) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!(
+            "{} needs a time such as 10s or 500ms, not '{}'",
+            flag, value
+        )),
+    }
+}
+
+fn parse_only_day<I: Iterator<Item = String>>(mut args: I) -> Result<u8, String> {
+    match (args.next(), args.next()) {
+        (Some(day), None) => parse_day(&day),
+        (None, _) => Err("missing day".to_string()),
+        (_, Some(extra)) => Err(format!("unexpected argument '{}'", extra)),
+    }
+}
+
+pub fn parse_day(day: &str) -> Result<u8, String> {
+    match day.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("'{}' is not a day between 1 and 25", day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn can_parse_run() {
+        assert_eq!(
+            Ok(Command::Run {
+                day: 3,
+                part: None,
+                input: None,
+                format: Format::Text,
+                explain: false,
+                timeout: None,
+                verbosity: 0,
+                trace_json: None
+            }),
+            parse_args(args("run 3"))
+        );
+        assert_eq!(
+            Ok(Command::Run {
+                day: 9,
+                part: Some(2),
+                input: Some(PathBuf::from("xmas.txt")),
+                format: Format::Json,
+                explain: true,
+                timeout: Some(Duration::from_millis(1500)),
+                verbosity: 2,
+                trace_json: Some(PathBuf::from("trace.jsonl"))
+            }),
+            parse_args(args(
+                "run 09 --part 2 --input xmas.txt --format json --explain --timeout 1.5 -vv \
+                 --trace-json trace.jsonl"
+            ))
+        );
+    }
+
+    #[test]
+    fn can_parse_all() {
+        assert_eq!(
+            Ok(Command::All {
+                format: Format::Text,
+                jobs: None,
+                timeout: None
+            }),
+            parse_args(args("all"))
+        );
+        assert_eq!(
+            Ok(Command::All {
+                format: Format::Tsv,
+                jobs: Some(2),
+                timeout: Some(Duration::from_secs(10))
+            }),
+            parse_args(args("all --format tsv --jobs 2 --timeout 10s"))
+        );
+        assert!(parse_args(args("all --jobs 0")).is_err());
+        assert!(parse_args(args("all --timeout")).is_err());
+    }
+
+    #[test]
+    fn can_parse_verify() {
+        assert_eq!(
+            Ok(Command::Verify {
+                accept: false,
+                timeout: None
+            }),
+            parse_args(args("verify"))
+        );
+        assert_eq!(
+            Ok(Command::Verify {
+                accept: true,
+                timeout: Some(Duration::from_millis(500))
+            }),
+            parse_args(args("verify --timeout 500ms --accept"))
+        );
+        assert!(parse_args(args("verify --accept 3")).is_err());
+    }
+
+    #[test]
+    fn can_parse_durations() {
+        let duration = |value: &str| parse_duration("--timeout", Some(value.to_string()));
+        assert_eq!(Ok(Duration::from_secs(10)), duration("10"));
+        assert_eq!(Ok(Duration::from_secs(10)), duration("10s"));
+        assert_eq!(Ok(Duration::from_millis(250)), duration("250ms"));
+        assert_eq!(Ok(Duration::from_millis(2500)), duration("2.5s"));
+        for bad in ["0", "-1s", "soon", "10m", "inf", "1e30", "NaN"].iter() {
+            assert!(duration(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn can_parse_day_commands() {
+        assert_eq!(
+            Ok(Command::NewDay { day: 7 }),
+            parse_args(args("new-day 7"))
+        );
+        assert!(parse_args(args("new-day")).is_err());
+        assert!(parse_args(args("new-day 0")).is_err());
+        assert!(parse_args(args("new-day 7 8")).is_err());
+        assert_eq!(Ok(Command::Fetch { day: 12 }), parse_args(args("fetch 12")));
+        assert!(parse_args(args("fetch")).is_err());
+        assert_eq!(Ok(Command::Watch { day: 9 }), parse_args(args("watch 9")));
+        assert_eq!(
+            Ok(Command::Repl {
+                day: 4,
+                input: Some(PathBuf::from("batch.txt"))
+            }),
+            parse_args(args("repl 4 --input batch.txt"))
+        );
+        assert!(parse_args(args("repl 4 --input")).is_err());
+        assert_eq!(
+            Ok(Command::Submit { day: 9, part: 2 }),
+            parse_args(args("submit 9 2"))
+        );
+        assert!(parse_args(args("submit 9")).is_err());
+        assert!(parse_args(args("submit 9 3")).is_err());
+    }
+
+    #[test]
+    fn can_parse_generate() {
+        assert_eq!(
+            Ok(Command::Generate {
+                day: 5,
+                size: 1000,
+                seed: 1
+            }),
+            parse_args(args("generate 5"))
+        );
+        assert_eq!(
+            Ok(Command::Generate {
+                day: 9,
+                size: 100000,
+                seed: 42
+            }),
+            parse_args(args("generate 9 --seed 42 --size 100000"))
+        );
+        assert!(parse_args(args("generate 9 --size")).is_err());
+        assert!(parse_args(args("generate 9 --seed -1")).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(parse_args(args("")).is_err());
+        assert!(parse_args(args("walk 3")).is_err());
+        assert!(parse_args(args("run")).is_err());
+        assert!(parse_args(args("run 26")).is_err());
+        assert!(parse_args(args("run 3 --part 3")).is_err());
+        assert!(parse_args(args("run 3 --part")).is_err());
+        assert!(parse_args(args("all 3")).is_err());
+        assert!(parse_args(args("all --format xml")).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const YEAR: u16 = 2020;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/xorspark/aoc2020-rust by xorspark@sparkant.com";
+
+/// Talks to the puzzle site as the user whose session cookie it holds.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .redirects(0)
+                .build(),
+        }
+    }
+
+    /// A client for `AOC_BASE_URL` (the real site by default) and the session
+    /// token from [`session_token`].
+    pub fn from_env() -> Result<Client, String> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Client::new(&base_url, &session_token()?))
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+        read_body(&url, response)
+    }
+
+    /// Posts a guess and returns the page the site answers with.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<String, String> {
+        let url = format!("{}/answer", self.day_url(day));
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &level), ("answer", answer)]);
+        read_body(&url, response)
+    }
+}
+
+fn read_body(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, String> {
+    let response = match response {
+        Ok(response) => response,
+        Err(ureq::Error::Status(400, _)) => {
+            return Err(format!("{} rejected the session token, log in again", url))
+        }
+        Err(ureq::Error::Status(404, _)) => {
+            return Err(format!(
+                "{} does not exist yet, is the puzzle unlocked?",
+                url
+            ))
+        }
+        Err(ureq::Error::Status(code, response)) => {
+            return Err(format!(
+                "{} answered {} {}",
+                url,
+                code,
+                response.status_text()
+            ))
+        }
+        Err(e) => return Err(format!("cannot reach the puzzle site: {}", e)),
+    };
+    if response.status() != 200 {
+        return Err(format!(
+            "{} answered {} {}, is the session token still valid?",
+            url,
+            response.status(),
+            response.status_text()
+        ));
+    }
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| format!("cannot read the answer from {}: {}", url, e))?;
+    Ok(body)
+}
+
+/// The `session` cookie of a logged in browser, from `AOC_SESSION` or else
+/// the file named by `AOC_SESSION_FILE`, `~/.config/aoc/session` by default.
+pub fn session_token() -> Result<String, String> {
+    match env::var("AOC_SESSION") {
+        Ok(token) if !token.trim().is_empty() => return Ok(token.trim().to_string()),
+        _ => {}
+    }
+    let path = match env::var_os("AOC_SESSION_FILE") {
+        Some(path) => PathBuf::from(path),
+        None => match env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(".config/aoc/session"),
+            None => return Err("no session token: set AOC_SESSION".to_string()),
+        },
+    };
+    match fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => Err(format!(
+            "no session token: set AOC_SESSION or put it in {}",
+            path.display()
+        )),
+    }
+}
+
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    /// A one-request HTTP server on localhost. Returns its base URL and the
+    /// raw request it received, head and body.
+    pub fn serve(status: &str, body: &str) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            sender.send(request).unwrap();
+        });
+        (base_url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_download_input() {
+        let (base_url, request) = stub::serve("200 OK", "1721\n979\n");
+        let client = Client::new(&base_url, "abc123");
+        assert_eq!("1721\n979\n", client.input(1).unwrap());
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("GET /2020/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+    }
+
+    #[test]
+    fn can_post_answer() {
+        let (base_url, request) =
+            stub::serve("200 OK", "<article>That's the right answer!</article>");
+        let client = Client::new(&base_url, "abc123");
+        assert!(client.submit(9, 2, "62").unwrap().contains("right answer"));
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("POST /2020/day/9/answer HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=62"));
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (base_url, _request) = stub::serve("400 Bad Request", "Please log in");
+        let error = Client::new(&base_url, "stale").input(1).unwrap_err();
+        assert!(error.contains("session token"), "{}", error);
+
+        let (base_url, _request) = stub::serve("404 Not Found", "Not yet");
+        let error = Client::new(&base_url, "abc123").input(25).unwrap_err();
+        assert!(error.contains("unlocked"), "{}", error);
+    }
+}
@@ -0,0 +1,103 @@
+use aoc_core::{registry, Solver};
+
+// The days add themselves to the registry, but only crates named somewhere get
+// linked into the runner.
+use aoc01 as _;
+use aoc02 as _;
+use aoc03 as _;
+use aoc04 as _;
+use aoc05 as _;
+use aoc06 as _;
+use aoc09 as _;
+
+/// Every day a linked crate registered with `#[aoc]`, in order.
+pub fn days() -> Vec<u8> {
+    registry::days()
+}
+
+pub fn solver(day: u8) -> Option<Box<dyn Solver>> {
+    registry::solver(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answers::{self, RecordedAnswers};
+    use aoc_core::fixtures;
+    use aoc_core::input::InputConfig;
+    use std::fs;
+    use std::path::Path;
+
+    // The `aocNN` crates in the workspace, whose days should all be linked in.
+    fn workspace_days() -> Vec<u8> {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../Cargo.toml");
+        let manifest = fs::read_to_string(workspace).unwrap();
+        let mut days: Vec<u8> = manifest
+            .lines()
+            .filter_map(|line| {
+                line.trim()
+                    .strip_prefix("\"aoc")?
+                    .strip_suffix("\",")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn can_find_every_solver() {
+        let days = days();
+        assert_eq!(workspace_days(), days);
+        // Days added by `new-day` come on top of these.
+        for day in [1, 2, 3, 4, 5, 6, 9].iter() {
+            assert!(days.contains(day), "day {} is not linked in", day);
+        }
+        for day in 1..=25 {
+            match solver(day) {
+                Some(solver) => assert_eq!(day, solver.day()),
+                None => assert!(!days.contains(&day), "no solver for day {}", day),
+            }
+        }
+    }
+
+    #[test]
+    fn can_find_every_input() {
+        let config = InputConfig::default();
+        for day in days() {
+            // A day fresh from `new-day` has neither input nor answers yet.
+            let recorded = answers::read_answers(&config.answers_path(day)).unwrap();
+            if recorded == RecordedAnswers::default() {
+                continue;
+            }
+            let path = config.default_path(day);
+            assert!(path.is_file(), "missing input for day {}", day);
+        }
+    }
+
+    #[test]
+    fn can_find_every_answers_file() {
+        for day in days() {
+            let path = InputConfig::default().answers_path(day);
+            assert!(path.is_file(), "no recorded answers for day {}", day);
+        }
+    }
+
+    #[test]
+    fn every_fixture_passes() {
+        let config = InputConfig::default();
+        let mut checked = 0;
+        let mut failures = Vec::new();
+        for day in days() {
+            for fixture in fixtures::load_fixtures(&config.input_dir(day)).unwrap() {
+                if let Err(message) = fixture.check(solver(day).unwrap().as_mut()) {
+                    failures.push(message);
+                }
+                checked += 1;
+            }
+        }
+        assert!(checked > 0, "no fixtures found");
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,84 @@
+use aoc_core::input::InputConfig;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the day's input file exists, calling `download` only when it
+/// does not. The file is written under a temporary name and renamed, so a
+/// failed download never leaves a partial input behind.
+pub fn fetch<F>(config: &InputConfig, day: u8, download: F) -> Result<Fetched, String>
+where
+    F: FnOnce(u8) -> Result<String, String>,
+{
+    let path = config.default_path(day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = download(day)?;
+    let dir = config.input_dir(day);
+    fs::create_dir_all(&dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    let partial = path.with_extension("partial");
+    fs::write(&partial, input).map_err(|e| format!("cannot write {}: {}", partial.display(), e))?;
+    fs::rename(&partial, &path).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{stub, Client};
+    use aoc_core::input::DEFAULT_FILE_NAME;
+    use std::env;
+    use std::process;
+
+    fn scratch_config(name: &str) -> InputConfig {
+        let root = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        InputConfig {
+            root,
+            file_name: DEFAULT_FILE_NAME.to_string(),
+        }
+    }
+
+    #[test]
+    fn downloads_once_then_uses_the_cache() {
+        let config = scratch_config("cache");
+        let (base_url, request) = stub::serve("200 OK", "35\n20\n15\n");
+        let client = Client::new(&base_url, "abc123");
+
+        let path = config.default_path(9);
+        assert_eq!(
+            Ok(Fetched::Downloaded(path.clone())),
+            fetch(&config, 9, |day| client.input(day))
+        );
+        assert!(request
+            .recv()
+            .unwrap()
+            .starts_with("GET /2020/day/9/input "));
+        assert_eq!("35\n20\n15\n", fs::read_to_string(&path).unwrap());
+
+        assert_eq!(
+            Ok(Fetched::Cached(path)),
+            fetch(&config, 9, |_| panic!(
+                "the cached input was downloaded again"
+            ))
+        );
+        fs::remove_dir_all(&config.root).unwrap();
+    }
+
+    #[test]
+    fn leaves_nothing_behind_on_failure() {
+        let config = scratch_config("failure");
+        let (base_url, _request) = stub::serve("500 Internal Server Error", "");
+        let client = Client::new(&base_url, "abc123");
+
+        assert!(fetch(&config, 9, |day| client.input(day)).is_err());
+        assert!(!config.default_path(9).exists());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::days;
+use aoc_core::Answer;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Why a part has no answer.
+#[derive(Debug, PartialEq)]
+pub enum Failure {
+    Error(String),
+    TimedOut { day: u8, part: u8, limit: Duration },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Error(message) => write!(f, "{}", message),
+            Failure::TimedOut { day, part, limit } => write!(
+                f,
+                "day {}, part {}: no answer within {:?}",
+                day, part, limit
+            ),
+        }
+    }
+}
+
+/// Solves `parts` of `day`, returning one result per part in the same order.
+///
+/// Without a `limit` the parts share one parse on the calling thread. With
+/// one, each part gets its own parse on its own thread and `limit` to itself,
+/// so two parts can take up to twice as long; a solver cannot be stopped from
+/// outside, so one that runs over is left to finish in the background and its
+/// answer thrown away.
+pub fn solve_parts(
+    day: u8,
+    input: &str,
+    parts: &[u8],
+    explain: bool,
+    limit: Option<Duration>,
+) -> Vec<Result<Answer, Failure>> {
+    let limit = match limit {
+        Some(limit) => limit,
+        None => {
+            return match solve(day, input, parts, explain) {
+                Ok(answers) => answers.into_iter().map(Ok).collect(),
+                Err(message) => parts
+                    .iter()
+                    .map(|_| Err(Failure::Error(message.clone())))
+                    .collect(),
+            }
+        }
+    };
+
+    parts
+        .iter()
+        .map(|&part| {
+            let input = input.to_string();
+            let solved = within(limit, move || solve(day, &input, &[part], explain));
+            match solved {
+                Some(Ok(mut answers)) => answers.pop().ok_or_else(|| {
+                    Failure::Error(format!("day {}, part {}: no answer", day, part))
+                }),
+                Some(Err(message)) => Err(Failure::Error(message)),
+                None => Err(Failure::TimedOut { day, part, limit }),
+            }
+        })
+        .collect()
+}
+
+fn solve(day: u8, input: &str, parts: &[u8], explain: bool) -> Result<Vec<Answer>, String> {
+    let solver = days::solver(day).ok_or(format!("day {} is not solved yet", day))?;
+    let answers = if explain {
+        solver.explain(input, parts)
+    } else {
+        solver.solve(input, parts)
+    };
+    answers.map_err(|e| e.in_day(day).to_string())
+}
+
+/// Runs `work` on a thread of its own and waits at most `limit` for it.
+pub fn within<R, F>(limit: Duration, work: F) -> Option<R>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(work));
+        // Nobody is listening any more once the limit has passed.
+        let _ = sender.send(result);
+    });
+    match receiver.recv_timeout(limit) {
+        Ok(Ok(result)) => Some(result),
+        // Passed on so a panic is reported the same with or without a limit.
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn gives_up_after_the_limit() {
+        assert_eq!(Some(4), within(Duration::from_secs(5), || 2 + 2));
+        let start = Instant::now();
+        let slow = within(Duration::from_millis(50), || {
+            thread::sleep(Duration::from_secs(5));
+            4
+        });
+        assert_eq!(None, slow);
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        let panicked = panic::catch_unwind(|| within(Duration::from_secs(5), || panic!("boom")));
+        assert!(panicked.is_err());
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn can_solve_parts_within_a_limit() {
+        let input = "1721\n979\n366\n299\n675\n1456\n";
+        for limit in [None, Some(Duration::from_secs(10))].iter() {
+            let results = solve_parts(1, input, &[2, 1], false, *limit);
+            let values: Vec<String> = results.into_iter().map(|r| r.unwrap().value).collect();
+            assert_eq!(vec!["241861950", "514579"], values);
+        }
+
+        let results = solve_parts(1, "x\n", &[1, 2], false, Some(Duration::from_secs(10)));
+        assert_eq!(2, results.len());
+        for result in results {
+            let failure = result.unwrap_err().to_string();
+            assert!(failure.starts_with("day 1, line 1"), "{}", failure);
+        }
+    }
+
+    #[test]
+    fn can_describe_a_timeout() {
+        let failure = Failure::TimedOut {
+            day: 9,
+            part: 2,
+            limit: Duration::from_millis(1500),
+        };
+        assert_eq!("day 9, part 2: no answer within 1.5s", failure.to_string());
+    }
+}
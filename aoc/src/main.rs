@@ -0,0 +1,415 @@
+mod args;
+mod client;
+mod days;
+mod fetch;
+mod limit;
+mod pool;
+mod scaffold;
+mod submit;
+mod summary;
+mod verify;
+mod watch;
+
+use aoc_core::answers;
+use aoc_core::input::InputConfig;
+use aoc_core::output::{self, Format};
+use aoc_core::repl;
+use aoc_core::trace;
+use aoc_core::{Answer, Error, Rng};
+use args::Command;
+use limit::Failure;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use summary::Row;
+
+fn main() {
+    let command = match args::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, args::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            explain,
+            timeout,
+            verbosity,
+            trace_json,
+        } => trace::start_from_args(verbosity, trace_json.as_deref())
+            .map_err(|e| format!("cannot start tracing: {}", e))
+            .and_then(|()| run_day(day, part, input, format, explain, timeout)),
+        Command::All {
+            format,
+            jobs,
+            timeout,
+        } => run_all(format, jobs, timeout),
+        Command::Verify { accept, timeout } => run_verify(accept, timeout),
+        Command::NewDay { day } => new_day(day),
+        Command::Fetch { day } => fetch_input(day),
+        Command::Submit { day, part } => submit_answer(day, part),
+        Command::Generate { day, size, seed } => generate_input(day, size, seed),
+        Command::Watch { day } => watch_day(day),
+        Command::Repl { day, input } => explore_day(day, input),
+    };
+    if let Err(e) = trace::stop() {
+        eprintln!("error: cannot write the trace: {}", e);
+    }
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn run_day(
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    format: Format,
+    explain: bool,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    days::solver(day).ok_or(format!("day {} is not solved yet", day))?;
+    let input = InputConfig::from_env()
+        .read_input(day, input.as_deref())
+        .map_err(|e| e.to_string())?;
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let mut answers = Vec::new();
+    let mut failures: Vec<String> = Vec::new();
+    for result in limit::solve_parts(day, &input, &parts, explain, timeout) {
+        match result {
+            Ok(answer) => answers.push(answer),
+            // Both parts fail the same way when the input does not parse.
+            Err(failure) if failures.contains(&failure.to_string()) => {}
+            Err(failure) => failures.push(failure.to_string()),
+        }
+    }
+    match format {
+        Format::Text => {
+            for answer in answers {
+                output::print_answer(answer.part, &format!("of day {}", day), &answer.value);
+                if let Some(explanation) = answer.explanation {
+                    print!("{}", output::format_explanation(&explanation));
+                }
+            }
+        }
+        Format::Json => print!("{}", output::format_json(&answers)),
+        Format::Tsv => print!("{}", output::format_tsv(&answers)),
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\nerror: "))
+    }
+}
+
+fn run_all(format: Format, jobs: Option<usize>, timeout: Option<Duration>) -> Result<(), String> {
+    let config = InputConfig::from_env();
+    let days = days::days();
+    let start = Instant::now();
+    let results = pool::run(
+        days.clone(),
+        jobs.unwrap_or_else(pool::default_workers),
+        |day| {
+            let start = Instant::now();
+            let results = match config.read_file(day, None) {
+                Ok(input) => limit::solve_parts(day, &input, &[1, 2], false, timeout),
+                Err(e) => {
+                    let message = Error::from(e).in_day(day).to_string();
+                    vec![
+                        Err(Failure::Error(message.clone())),
+                        Err(Failure::Error(message)),
+                    ]
+                }
+            };
+            (results, start.elapsed())
+        },
+    );
+    let wall_time = start.elapsed();
+
+    let mut all_answers: Vec<Answer> = Vec::new();
+    let mut rows = Vec::new();
+    let mut failures = Vec::new();
+    let mut failed_days = 0;
+    for (day, result) in days.into_iter().zip(results) {
+        let (results, elapsed) = result.unwrap_or_else(|message| {
+            let failure = || Err(Failure::Error(format!("day {}: {}", day, message)));
+            (vec![failure(), failure()], Duration::ZERO)
+        });
+        let mut cells = Vec::new();
+        let mut day_failures: Vec<String> = Vec::new();
+        for result in results {
+            match result {
+                Ok(answer) => {
+                    cells.push(answer.value.clone());
+                    all_answers.push(answer);
+                }
+                Err(failure) => {
+                    cells.push(match failure {
+                        Failure::TimedOut { .. } => "timeout".to_string(),
+                        Failure::Error(_) => "error".to_string(),
+                    });
+                    if !day_failures.contains(&failure.to_string()) {
+                        day_failures.push(failure.to_string());
+                    }
+                }
+            }
+        }
+        if !day_failures.is_empty() {
+            failed_days += 1;
+            failures.extend(day_failures);
+        }
+        rows.push(Row {
+            day,
+            part_one: cells[0].clone(),
+            part_two: cells[1].clone(),
+            elapsed,
+        });
+    }
+    match format {
+        Format::Text => {
+            print!("{}", summary::format_table(&rows));
+            println!("{}", summary::format_total(&rows, wall_time));
+        }
+        Format::Json => print!("{}", output::format_json(&all_answers)),
+        Format::Tsv => print!("{}", output::format_tsv(&all_answers)),
+    }
+
+    for failure in failures.iter() {
+        eprintln!("error: {}", failure);
+    }
+    if failed_days == 0 {
+        Ok(())
+    } else {
+        Err(format!("{} of {} days failed", failed_days, rows.len()))
+    }
+}
+
+fn run_verify(accept: bool, timeout: Option<Duration>) -> Result<(), String> {
+    let config = InputConfig::from_env();
+    let mut checks = Vec::new();
+    for day in days::days() {
+        let path = config.answers_path(day);
+        let read = answers::read_answers(&path)
+            .and_then(|recorded| Ok((recorded, config.read_file(day, None)?)));
+        let (mut recorded, input) = match read {
+            Ok(read) => read,
+            Err(e) => {
+                let message = e.in_day(day).to_string();
+                for part in 1..=2 {
+                    checks.push(verify::Check {
+                        day,
+                        part,
+                        outcome: verify::Outcome::Failed(message.clone()),
+                    });
+                }
+                continue;
+            }
+        };
+
+        let mut answers = Vec::new();
+        let mut failed = Vec::new();
+        let results = limit::solve_parts(day, &input, &[1, 2], false, timeout);
+        for (part, result) in (1..=2).zip(results) {
+            match result {
+                Ok(answer) => answers.push(answer),
+                Err(failure) => failed.push(verify::Check {
+                    day,
+                    part,
+                    outcome: verify::Outcome::Failed(failure.to_string()),
+                }),
+            }
+        }
+        let mut day_checks = verify::compare(day, &recorded, &answers);
+        if accept && verify::accept(&mut recorded, &mut day_checks) {
+            fs::write(&path, recorded.to_string())
+                .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        }
+        day_checks.extend(failed);
+        day_checks.sort_by_key(|check| check.part);
+        checks.extend(day_checks);
+    }
+    print!("{}", verify::format_report(&checks));
+
+    let failures = checks.iter().filter(|c| verify::is_failure(c)).count();
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} answers did not verify",
+            failures,
+            checks.len()
+        ))
+    }
+}
+
+fn new_day(day: u8) -> Result<(), String> {
+    let root = InputConfig::from_env().root;
+    for path in scaffold::new_day(&root, day)? {
+        println!("wrote {}", path.display());
+    }
+    println!(
+        "Put the puzzle input in aoc{:02}/input/input.txt, then `aoc run {}`.",
+        day, day
+    );
+    Ok(())
+}
+
+fn fetch_input(day: u8) -> Result<(), String> {
+    let config = InputConfig::from_env();
+    match fetch::fetch(&config, day, |day| client::Client::from_env()?.input(day))? {
+        fetch::Fetched::Cached(path) => println!("{} is already downloaded", path.display()),
+        fetch::Fetched::Downloaded(path) => println!("saved {}", path.display()),
+    }
+    Ok(())
+}
+
+fn submit_answer(day: u8, part: u8) -> Result<(), String> {
+    let config = InputConfig::from_env();
+    let solver = days::solver(day).ok_or(format!("day {} is not solved yet", day))?;
+    let input = config
+        .read_file(day, None)
+        .map_err(|e| Error::from(e).in_day(day).to_string())?;
+    let answer = solver
+        .solve(&input, &[part])
+        .map_err(|e| e.to_string())?
+        .remove(0)
+        .value;
+
+    let log_path = config.root.join(submit::LOG_FILE_NAME);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    submit::check_guess(&submit::read_log(&log_path)?, day, part, &answer, now)?;
+
+    let page = client::Client::from_env()?.submit(day, part, &answer)?;
+    let response = submit::parse_response(&page);
+    let record = submit::Record {
+        time: now,
+        day,
+        part,
+        answer: answer.clone(),
+        verdict: response.verdict,
+        wait: response.wait,
+    };
+    submit::append_to_log(&log_path, &record)?;
+
+    let wait = match response.wait {
+        Some(wait) => format!(" (next guess in {}s)", wait),
+        None => String::new(),
+    };
+    match response.verdict {
+        submit::Verdict::Correct => {
+            let answers_path = config.answers_path(day);
+            let mut recorded = answers::read_answers(&answers_path).map_err(|e| e.to_string())?;
+            if recorded.get(part).is_none() {
+                recorded.set(part, answer.clone());
+                fs::write(&answers_path, recorded.to_string())
+                    .map_err(|e| format!("cannot write {}: {}", answers_path.display(), e))?;
+            }
+            println!("{} is correct for day {} part {}", answer, day, part);
+            Ok(())
+        }
+        verdict => Err(format!(
+            "{} for day {} part {}: {}{}",
+            answer, day, part, verdict, wait
+        )),
+    }
+}
+
+fn generate_input(day: u8, size: usize, seed: u64) -> Result<(), String> {
+    let solver = days::solver(day).ok_or(format!("day {} is not solved yet", day))?;
+    let input = solver
+        .generate(&mut Rng::new(seed), size)
+        .ok_or(format!("day {} has no input generator", day))?;
+    print!("{}", input);
+    Ok(())
+}
+
+fn explore_day(day: u8, input: Option<PathBuf>) -> Result<(), String> {
+    let solver = days::solver(day).ok_or(format!("day {} is not solved yet", day))?;
+    let input = InputConfig::from_env()
+        .read_file(day, input.as_deref())
+        .map_err(|e| e.to_string())?;
+    let explorer = solver.explorer(&input).map_err(|e| e.to_string())?;
+    println!("day {} is loaded, `help` lists the commands", day);
+    repl::run(
+        &*explorer,
+        &format!("day{:02}> ", day),
+        &mut io::stdin().lock(),
+        &mut io::stdout(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// Polls rather than subscribing to file events, which is plenty for a couple
+// of small directories and needs nothing from the platform.
+fn watch_day(day: u8) -> Result<(), String> {
+    let config = InputConfig::from_env();
+    let day_dir = config.day_dir(day);
+    if !day_dir.is_dir() {
+        return Err(format!("{} does not exist", day_dir.display()));
+    }
+    let dirs = watch::watched_dirs(&day_dir);
+    let crate_name = format!("aoc{:02}", day);
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let command = |args: &[&str]| {
+        let mut command = process::Command::new(&cargo);
+        command
+            .args(args)
+            .current_dir(&config.root)
+            .stdin(process::Stdio::null());
+        command
+    };
+
+    let mut previous = Vec::new();
+    let mut seen = watch::snapshot(&dirs).map_err(|e| e.to_string())?;
+    loop {
+        let tests = command(&["test", "-q", "-p", &crate_name])
+            .status()
+            .map_err(|e| format!("cannot run {}: {}", cargo, e))?;
+        if !tests.success() {
+            println!("tests failed");
+        }
+        let run = command(&["run", "-q", "-p", &crate_name, "--", "--format", "tsv"])
+            .stderr(process::Stdio::inherit())
+            .output()
+            .map_err(|e| format!("cannot run {}: {}", cargo, e))?;
+        if run.status.success() {
+            let current = watch::parse_tsv_answers(&String::from_utf8_lossy(&run.stdout));
+            for line in watch::diff_answers(&previous, &current) {
+                println!("{}", line);
+            }
+            previous = current;
+        } else {
+            println!("day {} failed", day);
+        }
+        println!("watching {} for changes", day_dir.display());
+
+        loop {
+            thread::sleep(Duration::from_millis(500));
+            let now = watch::snapshot(&dirs).map_err(|e| e.to_string())?;
+            let changed = watch::changed(&seen, &now);
+            seen = now;
+            if !changed.is_empty() {
+                for path in changed {
+                    println!("changed {}", path.display());
+                }
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+/// Runs `work` on every job with up to `workers` threads and returns the
+/// results in job order. A job that panics becomes an `Err` with the panic
+/// message, and the other jobs carry on.
+pub fn run<T, R, F>(jobs: Vec<T>, workers: usize, work: F) -> Vec<Result<R, String>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let count = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, count.max(1)) {
+            let sender = sender.clone();
+            let (queue, work) = (&queue, &work);
+            scope.spawn(move || loop {
+                let next = queue.lock().unwrap().next();
+                let (idx, job) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| work(job)));
+                sender.send((idx, result.map_err(panic_message))).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<Option<Result<R, String>>> = (0..count).map(|_| None).collect();
+    for (idx, result) in receiver {
+        results[idx] = Some(result);
+    }
+    results.into_iter().map(Option::unwrap).collect()
+}
+
+/// One worker per core, as far as the standard library can tell.
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", message)
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn keeps_job_order() {
+        let results = run(vec![30, 1, 20, 2], 4, |ms| {
+            thread::sleep(Duration::from_millis(ms));
+            ms * 2
+        });
+        assert_eq!(vec![Ok(60), Ok(2), Ok(40), Ok(4)], results);
+        assert!(run(Vec::<u8>::new(), 4, |n| n).is_empty());
+    }
+
+    #[test]
+    fn panics_do_not_hide_other_jobs() {
+        let results = run(vec![1, 2, 3], 2, |n| {
+            if n == 2 {
+                panic!("day {} exploded", n);
+            }
+            n
+        });
+        assert_eq!(
+            vec![Ok(1), Err("panicked: day 2 exploded".to_string()), Ok(3)],
+            results
+        );
+    }
+
+    #[test]
+    fn runs_jobs_concurrently() {
+        let start = Instant::now();
+        let results = run(vec![Duration::from_millis(200); 4], 4, thread::sleep);
+        assert_eq!(4, results.len());
+        assert!(start.elapsed() < Duration::from_millis(700));
+    }
+}
@@ -0,0 +1,205 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+const SAMPLE_ANSWERS: &str =
+    "# The sample's answers, checked by the runner's fixture test.\n# part1: \n# part2: \n";
+
+/// Creates the `aocNN` crate for `day` under the workspace `root` and wires it
+/// into the workspace, the runner and the benches. Returns every path written.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let name = format!("aoc{:02}", day);
+    let crate_dir = root.join(&name);
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+
+    // Work out every edit before touching the disk, so a tree we do not
+    // understand is left alone.
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("aoc").join("Cargo.toml");
+    let days = root.join("aoc").join("src").join("days.rs");
+    let benches = root.join("aoc").join("benches").join("days.rs");
+    let edits = vec![
+        (
+            workspace.clone(),
+            add_workspace_member(&read(&workspace)?, day)?,
+        ),
+        (runner.clone(), add_dependency(&read(&runner)?, day)?),
+        (days.clone(), register_solver(&read(&days)?, day)?),
+        (benches.clone(), register_bench(&read(&benches)?, day)?),
+    ];
+
+    let files = vec![
+        (crate_dir.join("Cargo.toml"), render(CARGO_TOML, day)),
+        (crate_dir.join("src").join("lib.rs"), render(LIB_RS, day)),
+        (crate_dir.join("src").join("main.rs"), render(MAIN_RS, day)),
+        (crate_dir.join("answers.txt"), String::new()),
+        (crate_dir.join("input").join("test.txt"), String::new()),
+        (
+            crate_dir.join("input").join("test.answers"),
+            SAMPLE_ANSWERS.to_string(),
+        ),
+    ];
+    let mut written = Vec::new();
+    create_dir(&crate_dir.join("src"))?;
+    create_dir(&crate_dir.join("input"))?;
+    for (path, contents) in files.into_iter().chain(edits) {
+        fs::write(&path, contents)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))
+}
+
+fn create_dir(path: &Path) -> Result<(), String> {
+    fs::create_dir_all(path).map_err(|e| format!("cannot create {}: {}", path.display(), e))
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{day:02}}", &format!("{:02}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+fn add_workspace_member(manifest: &str, day: u8) -> Result<String, String> {
+    let line = format!("    \"aoc{:02}\",", day);
+    insert_sorted(manifest, &line, day, |l| {
+        day_of(l.trim().strip_prefix("\"aoc")?.strip_suffix("\",")?)
+    })
+}
+
+fn add_dependency(manifest: &str, day: u8) -> Result<String, String> {
+    let line = format!("aoc{:02} = {{ path = \"../aoc{:02}\" }}", day, day);
+    insert_sorted(manifest, &line, day, |l| {
+        day_of(l.strip_prefix("aoc")?.split(' ').next()?)
+    })
+}
+
+fn register_bench(bench: &str, day: u8) -> Result<String, String> {
+    let line = format!("    bench_day(c, aoc{:02}::Day{:02});", day, day);
+    insert_sorted(bench, &line, day, |l| {
+        day_of(l.trim().strip_prefix("bench_day(c, aoc")?.get(..2)?)
+    })
+}
+
+fn register_solver(days: &str, day: u8) -> Result<String, String> {
+    let line = format!("use aoc{:02} as _;", day);
+    insert_sorted(days, &line, day, |l| {
+        day_of(l.strip_prefix("use aoc")?.strip_suffix(" as _;")?)
+    })
+}
+
+fn day_of(text: &str) -> Option<u8> {
+    text.parse().ok()
+}
+
+/// Puts `new_line` among the lines `day_of_line` recognises, keeping them in
+/// day order.
+fn insert_sorted<F>(text: &str, new_line: &str, day: u8, day_of_line: F) -> Result<String, String>
+where
+    F: Fn(&str) -> Option<u8>,
+{
+    let mut lines: Vec<&str> = text.lines().collect();
+    let mut position = None;
+    for (idx, line) in lines.iter().enumerate() {
+        match day_of_line(line) {
+            Some(existing) if existing == day => {
+                return Err(format!("day {} is already registered", day))
+            }
+            Some(existing) if existing > day => {
+                position = Some(idx);
+                break;
+            }
+            Some(_) => position = Some(idx + 1),
+            None => {}
+        }
+    }
+    let position =
+        position.ok_or_else(|| format!("cannot find where to add `{}`", new_line.trim()))?;
+    lines.insert(position, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn can_add_workspace_member() {
+        let manifest =
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc01\",\n    \"aoc09\",\n]\n";
+        assert_eq!(
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc01\",\n    \"aoc07\",\n    \"aoc09\",\n]\n",
+            add_workspace_member(manifest, 7).unwrap()
+        );
+        assert_eq!(
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc01\",\n    \"aoc09\",\n    \"aoc10\",\n]\n",
+            add_workspace_member(manifest, 10).unwrap()
+        );
+        assert!(add_workspace_member(manifest, 9).is_err());
+    }
+
+    #[test]
+    fn can_register_solver() {
+        let days =
+            "use aoc_core::Solver;\n\nuse aoc01 as _;\nuse aoc09 as _;\n\npub fn days() {}\n";
+        assert_eq!(
+            "use aoc_core::Solver;\n\nuse aoc01 as _;\nuse aoc07 as _;\nuse aoc09 as _;\n\npub fn days() {}\n",
+            register_solver(days, 7).unwrap()
+        );
+        assert!(register_solver(days, 9).is_err());
+    }
+
+    #[test]
+    fn can_register_dependency_and_bench() {
+        assert_eq!(
+            "[dependencies]\naoc-core = { path = \"../aoc-core\" }\naoc01 = { path = \"../aoc01\" }\naoc02 = { path = \"../aoc02\" }\n",
+            add_dependency(
+                "[dependencies]\naoc-core = { path = \"../aoc-core\" }\naoc01 = { path = \"../aoc01\" }\n",
+                2
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "    bench_day(c, aoc01::Day01);\n    bench_day(c, aoc02::Day02);\n}\n",
+            register_bench("    bench_day(c, aoc01::Day01);\n}\n", 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_scaffold_into_a_copy_of_the_workspace() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let root = env::temp_dir().join(format!("aoc-new-day-{}", process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::create_dir_all(root.join("aoc/benches")).unwrap();
+        for file in &[
+            "Cargo.toml",
+            "aoc/Cargo.toml",
+            "aoc/src/days.rs",
+            "aoc/benches/days.rs",
+        ] {
+            fs::copy(workspace.join(file), root.join(file)).unwrap();
+        }
+
+        let written = new_day(&root, 25).unwrap();
+        assert!(written.contains(&root.join("aoc25/src/lib.rs")));
+        assert!(written.contains(&root.join("aoc25/input/test.answers")));
+        let lib = fs::read_to_string(root.join("aoc25/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day25;"));
+        assert!(lib.contains("#[aoc(day = 25)]\n"));
+        let days = fs::read_to_string(root.join("aoc/src/days.rs")).unwrap();
+        assert!(days.contains(" as _;\nuse aoc25 as _;\n"));
+
+        assert!(new_day(&root, 25).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
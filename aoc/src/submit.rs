@@ -0,0 +1,356 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+pub const LOG_FILE_NAME: &str = "submissions.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The previous guess was too recent, this one was not looked at.
+    TooSoon,
+    /// The part is already solved, or part two is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+const VERDICTS: [(Verdict, &str); 7] = [
+    (Verdict::Correct, "correct"),
+    (Verdict::TooHigh, "too-high"),
+    (Verdict::TooLow, "too-low"),
+    (Verdict::Wrong, "wrong"),
+    (Verdict::TooSoon, "too-soon"),
+    (Verdict::WrongLevel, "wrong-level"),
+    (Verdict::Unknown, "unknown"),
+];
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (_, name) = VERDICTS.iter().find(|(v, _)| v == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+impl std::str::FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Verdict, String> {
+        VERDICTS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(v, _)| *v)
+            .ok_or_else(|| format!("unknown verdict `{}`", name))
+    }
+}
+
+/// What the site said about a guess, and how many seconds it wants us to
+/// wait before the next one.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub wait: Option<u64>,
+}
+
+pub fn parse_response(html: &str) -> Response {
+    let text = match (html.find("<article>"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else {
+        Verdict::Unknown
+    };
+    Response {
+        verdict,
+        wait: parse_wait(text),
+    }
+}
+
+// "You have 1m 5s left to wait." or "Please wait one minute before trying again."
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some(start) = text.find("You have ") {
+        let rest = &text[start + "You have ".len()..];
+        let left = &rest[..rest.find(" left")?];
+        let mut seconds = 0;
+        for token in left.split_whitespace() {
+            let (number, unit) = token.split_at(token.len().checked_sub(1)?);
+            let number: u64 = number.parse().ok()?;
+            seconds += match unit {
+                "h" => number * 3600,
+                "m" => number * 60,
+                "s" => number,
+                _ => return None,
+            };
+        }
+        return Some(seconds);
+    }
+    let start = text.find("lease wait ")?;
+    let mut words = text[start + "lease wait ".len()..].split_whitespace();
+    let number = match words.next()? {
+        "one" => 1,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse().ok()?,
+    };
+    match words.next()? {
+        "minute" | "minutes" => Some(number * 60),
+        "second" | "seconds" => Some(number),
+        _ => None,
+    }
+}
+
+/// One guess in the submissions log, a tab separated line of
+/// `time day part answer verdict wait` with the time in Unix seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub wait: Option<u64>,
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let wait = self.wait.map_or("-".to_string(), |w| w.to_string());
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.time, self.day, self.part, self.answer, self.verdict, wait
+        )
+    }
+}
+
+impl std::str::FromStr for Record {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Record, String> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let bad = || format!("cannot read submission `{}`", line);
+        if fields.len() != 6 {
+            return Err(bad());
+        }
+        Ok(Record {
+            time: fields[0].parse().map_err(|_| bad())?,
+            day: fields[1].parse().map_err(|_| bad())?,
+            part: fields[2].parse().map_err(|_| bad())?,
+            answer: fields[3].to_string(),
+            verdict: fields[4].parse()?,
+            wait: match fields[5] {
+                "-" => None,
+                wait => Some(wait.parse().map_err(|_| bad())?),
+            },
+        })
+    }
+}
+
+pub fn read_log(path: &Path) -> Result<Vec<Record>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+    };
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.parse())
+        .collect()
+}
+
+pub fn append_to_log(path: &Path, record: &Record) -> Result<(), String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut log| writeln!(log, "{}", record))
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+/// Refuses a guess the log already tells us the outcome of, and any guess
+/// while the site still wants us to wait.
+pub fn check_guess(
+    log: &[Record],
+    day: u8,
+    part: u8,
+    answer: &str,
+    now: u64,
+) -> Result<(), String> {
+    if let Some(wait_until) = log.iter().filter_map(|r| Some(r.time + r.wait?)).max() {
+        if wait_until > now {
+            return Err(format!(
+                "the site wants {} more seconds before the next guess",
+                wait_until - now
+            ));
+        }
+    }
+
+    // Only verdicts about the answer itself say anything about sending it again.
+    let unjudged = [Verdict::TooSoon, Verdict::WrongLevel, Verdict::Unknown];
+    let guesses = log
+        .iter()
+        .filter(|r| r.day == day && r.part == part && !unjudged.contains(&r.verdict));
+    let number: Option<i128> = answer.parse().ok();
+    for guess in guesses {
+        let guessed: Option<i128> = guess.answer.parse().ok();
+        let (refusal, detail) = match (guess.verdict, number, guessed) {
+            (Verdict::Correct, _, _) => ("the part is already solved with", &guess.answer),
+            _ if guess.answer == answer => (
+                "already guessed, and the site said it was",
+                &guess.verdict.to_string(),
+            ),
+            (Verdict::TooHigh, Some(number), Some(guessed)) if number >= guessed => (
+                "it cannot be right, the site said this was too high:",
+                &guess.answer,
+            ),
+            (Verdict::TooLow, Some(number), Some(guessed)) if number <= guessed => (
+                "it cannot be right, the site said this was too low:",
+                &guess.answer,
+            ),
+            _ => continue,
+        };
+        return Err(format!(
+            "not submitting {} for day {} part {}: {} {}",
+            answer, day, part, refusal, detail
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(time: u64, answer: &str, verdict: Verdict, wait: Option<u64>) -> Record {
+        Record {
+            time,
+            day: 9,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            wait,
+        }
+    }
+
+    #[test]
+    fn can_parse_responses() {
+        let page = |text: &str| {
+            format!(
+                "<html><main><article><p>{}</p></article></main></html>",
+                text
+            )
+        };
+        assert_eq!(
+            Response {
+                verdict: Verdict::Correct,
+                wait: None
+            },
+            parse_response(&page(
+                "That's the right answer! You are one gold star closer."
+            ))
+        );
+        assert_eq!(
+            Response {
+                verdict: Verdict::TooHigh,
+                wait: Some(60)
+            },
+            parse_response(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again."
+            ))
+        );
+        assert_eq!(
+            Verdict::TooLow,
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+            .verdict
+        );
+        assert_eq!(
+            Response {
+                verdict: Verdict::Wrong,
+                wait: Some(300)
+            },
+            parse_response(&page(
+                "That's not the right answer. Please wait 5 minutes before trying again."
+            ))
+        );
+        assert_eq!(
+            Response {
+                verdict: Verdict::TooSoon,
+                wait: Some(65)
+            },
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            ))
+        );
+        assert_eq!(
+            Verdict::WrongLevel,
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .verdict
+        );
+        assert_eq!(Verdict::Unknown, parse_response("<html></html>").verdict);
+    }
+
+    #[test]
+    fn can_round_trip_records() {
+        let record = record(1607500000, "127", Verdict::TooLow, Some(60));
+        assert_eq!("1607500000\t9\t1\t127\ttoo-low\t60", record.to_string());
+        assert_eq!(Ok(record.clone()), record.to_string().parse());
+        assert!("1607500000\t9\t1\t127\tmaybe\t-".parse::<Record>().is_err());
+    }
+
+    #[test]
+    fn refuses_guesses_with_known_outcomes() {
+        let log = vec![
+            record(100, "500", Verdict::TooHigh, Some(60)),
+            record(200, "100", Verdict::TooLow, Some(60)),
+            record(300, "250", Verdict::Wrong, Some(60)),
+        ];
+        assert!(check_guess(&log, 9, 1, "300", 1000).is_ok());
+        assert!(check_guess(&log, 9, 1, "250", 1000).is_err());
+        assert!(check_guess(&log, 9, 1, "600", 1000).is_err());
+        assert!(check_guess(&log, 9, 1, "50", 1000).is_err());
+        assert!(check_guess(&log, 9, 2, "600", 1000).is_ok());
+
+        let solved = vec![record(100, "127", Verdict::Correct, None)];
+        assert!(check_guess(&solved, 9, 1, "128", 1000).is_err());
+    }
+
+    #[test]
+    fn refuses_guesses_while_rate_limited() {
+        let log = vec![record(1000, "500", Verdict::TooSoon, Some(30))];
+        let error = check_guess(&log, 1, 1, "7", 1010).unwrap_err();
+        assert!(error.contains("20 more seconds"), "{}", error);
+        assert!(check_guess(&log, 1, 1, "7", 1030).is_ok());
+        // A guess that was not looked at can be sent again.
+        assert!(check_guess(&log, 9, 1, "500", 1030).is_ok());
+    }
+
+    #[test]
+    fn resends_guesses_the_site_did_not_judge() {
+        let log = vec![
+            record(100, "500", Verdict::WrongLevel, None),
+            record(200, "600", Verdict::Unknown, None),
+        ];
+        assert!(check_guess(&log, 9, 1, "500", 1000).is_ok());
+        assert!(check_guess(&log, 9, 1, "600", 1000).is_ok());
+    }
+}
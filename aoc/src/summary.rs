@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+pub struct Row {
+    pub day: u8,
+    pub part_one: String,
+    pub part_two: String,
+    pub elapsed: Duration,
+}
+
+pub fn format_table(rows: &[Row]) -> String {
+    let header = ["Day", "Part 1", "Part 2", "Time"];
+    let mut cells: Vec<[String; 4]> = vec![[
+        header[0].to_string(),
+        header[1].to_string(),
+        header[2].to_string(),
+        header[3].to_string(),
+    ]];
+    for row in rows {
+        cells.push([
+            row.day.to_string(),
+            row.part_one.clone(),
+            row.part_two.clone(),
+            format!("{:.2?}", row.elapsed),
+        ]);
+    }
+
+    let mut widths = [0; 4];
+    for line in cells.iter() {
+        for (width, cell) in widths.iter_mut().zip(line.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = String::new();
+    for line in cells.iter() {
+        let formatted: Vec<String> = line
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(formatted.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// The wall time of the whole run next to the time the days took one by one,
+/// which is what a serial run would have cost.
+pub fn format_total(rows: &[Row], wall_time: Duration) -> String {
+    let serial: Duration = rows.iter().map(|row| row.elapsed).sum();
+    format!(
+        "{} days in {:.2?} ({:.2?} one after another)",
+        rows.len(),
+        wall_time,
+        serial
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_format_table() {
+        let rows = vec![
+            Row {
+                day: 1,
+                part_one: "514579".to_string(),
+                part_two: "241861950".to_string(),
+                elapsed: Duration::from_millis(2),
+            },
+            Row {
+                day: 9,
+                part_one: "127".to_string(),
+                part_two: "62".to_string(),
+                elapsed: Duration::from_micros(1500),
+            },
+        ];
+        assert_eq!(
+            "\
+Day  Part 1  Part 2     Time
+1    514579  241861950  2.00ms
+9    127     62         1.50ms
+",
+            format_table(&rows)
+        );
+        assert_eq!(
+            "2 days in 2.10ms (3.50ms one after another)",
+            format_total(&rows, Duration::from_micros(2100))
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use aoc_core::answers::RecordedAnswers;
+use aoc_core::Answer;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Match,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// Nothing recorded yet; `accepted` once written to the answers file.
+    New {
+        actual: String,
+        accepted: bool,
+    },
+    Failed(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+pub fn compare(day: u8, recorded: &RecordedAnswers, answers: &[Answer]) -> Vec<Check> {
+    answers
+        .iter()
+        .map(|answer| {
+            let outcome = match recorded.get(answer.part) {
+                Some(expected) if expected == answer.value => Outcome::Match,
+                Some(expected) => Outcome::Mismatch {
+                    expected: expected.to_string(),
+                    actual: answer.value.clone(),
+                },
+                None => Outcome::New {
+                    actual: answer.value.clone(),
+                    accepted: false,
+                },
+            };
+            Check {
+                day,
+                part: answer.part,
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Records every new answer and returns whether there was any.
+pub fn accept(recorded: &mut RecordedAnswers, checks: &mut [Check]) -> bool {
+    let mut changed = false;
+    for check in checks.iter_mut() {
+        if let Outcome::New { actual, accepted } = &mut check.outcome {
+            recorded.set(check.part, actual.clone());
+            *accepted = true;
+            changed = true;
+        }
+    }
+    changed
+}
+
+pub fn format_report(checks: &[Check]) -> String {
+    let mut report = String::new();
+    let (mut matched, mut mismatched, mut new, mut failed) = (0, 0, 0, 0);
+    for check in checks {
+        let status = match &check.outcome {
+            Outcome::Match => {
+                matched += 1;
+                "ok".to_string()
+            }
+            Outcome::Mismatch { expected, actual } => {
+                mismatched += 1;
+                format!("MISMATCH: expected {}, got {}", expected, actual)
+            }
+            Outcome::New { actual, accepted } => {
+                new += 1;
+                if *accepted {
+                    format!("new answer {} (recorded)", actual)
+                } else {
+                    format!("new answer {}", actual)
+                }
+            }
+            Outcome::Failed(message) => {
+                failed += 1;
+                format!("FAILED: {}", message)
+            }
+        };
+        report.push_str(&format!(
+            "day {} part {}: {}\n",
+            check.day, check.part, status
+        ));
+    }
+    report.push_str(&format!(
+        "{} ok, {} mismatched, {} new, {} failed\n",
+        matched, mismatched, new, failed
+    ));
+    report
+}
+
+pub fn is_failure(check: &Check) -> bool {
+    matches!(check.outcome, Outcome::Mismatch { .. } | Outcome::Failed(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn answer(part: u8, value: &str) -> Answer {
+        Answer {
+            day: 9,
+            part,
+            value: value.to_string(),
+            parse_time: Duration::default(),
+            solve_time: Duration::default(),
+            explanation: None,
+        }
+    }
+
+    #[test]
+    fn can_compare_answers() {
+        let recorded = RecordedAnswers {
+            part_one: Some("127".to_string()),
+            part_two: None,
+        };
+        let checks = compare(9, &recorded, &[answer(1, "127"), answer(2, "62")]);
+        assert_eq!(Outcome::Match, checks[0].outcome);
+        assert_eq!(
+            Outcome::New {
+                actual: "62".to_string(),
+                accepted: false
+            },
+            checks[1].outcome
+        );
+
+        let checks = compare(9, &recorded, &[answer(1, "128")]);
+        assert!(is_failure(&checks[0]));
+    }
+
+    #[test]
+    fn can_accept_new_answers() {
+        let mut recorded = RecordedAnswers {
+            part_one: Some("127".to_string()),
+            part_two: None,
+        };
+        let mut checks = compare(9, &recorded, &[answer(1, "128"), answer(2, "62")]);
+        assert!(accept(&mut recorded, &mut checks));
+        assert_eq!(Some("127"), recorded.get(1));
+        assert_eq!(Some("62"), recorded.get(2));
+        assert!(!accept(&mut recorded, &mut checks[..1]));
+    }
+
+    #[test]
+    fn can_format_report() {
+        let checks = vec![
+            Check {
+                day: 1,
+                part: 1,
+                outcome: Outcome::Match,
+            },
+            Check {
+                day: 1,
+                part: 2,
+                outcome: Outcome::Mismatch {
+                    expected: "1".to_string(),
+                    actual: "2".to_string(),
+                },
+            },
+            Check {
+                day: 3,
+                part: 1,
+                outcome: Outcome::New {
+                    actual: "7".to_string(),
+                    accepted: true,
+                },
+            },
+        ];
+        assert_eq!(
+            "\
+day 1 part 1: ok
+day 1 part 2: MISMATCH: expected 1, got 2
+day 3 part 1: new answer 7 (recorded)
+1 ok, 1 mismatched, 1 new, 0 failed
+",
+            format_report(&checks)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modification time and length of every file under some directories.
+pub type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+/// Walks `dirs` recursively. A directory that does not exist is skipped, so
+/// watching can start before `input/` is created.
+pub fn snapshot(dirs: &[PathBuf]) -> io::Result<Snapshot> {
+    let mut files = Snapshot::new();
+    let mut pending: Vec<PathBuf> = dirs.to_vec();
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                files.insert(entry.path(), (metadata.modified()?, metadata.len()));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Files that were added, removed or modified between two snapshots.
+pub fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(stamp))
+        .map(|(path, _)| path.clone())
+        .collect();
+    paths.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    paths.sort();
+    paths
+}
+
+/// The `(part, answer)` pairs of a day binary's `--format tsv` output.
+pub fn parse_tsv_answers(output: &str) -> Vec<(u8, String)> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split('\t').skip(1);
+            let part = fields.next()?.parse().ok()?;
+            Some((part, fields.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// One line per part saying how its answer compares with the previous run.
+pub fn diff_answers(previous: &[(u8, String)], current: &[(u8, String)]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (part, answer) in current {
+        let before = previous.iter().find(|(p, _)| p == part).map(|(_, a)| a);
+        lines.push(match before {
+            Some(before) if before == answer => format!("part {}: {} (unchanged)", part, answer),
+            Some(before) => format!("part {}: {} (was {})", part, answer, before),
+            None => format!("part {}: {} (new)", part, answer),
+        });
+    }
+    for (part, before) in previous {
+        if !current.iter().any(|(p, _)| p == part) {
+            lines.push(format!("part {}: no answer (was {})", part, before));
+        }
+    }
+    lines
+}
+
+pub fn watched_dirs(day_dir: &Path) -> Vec<PathBuf> {
+    vec![day_dir.join("src"), day_dir.join("input")]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn can_detect_changes() {
+        let root = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        let dirs = watched_dirs(&root);
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::write(root.join("src/lib.rs"), "// one").unwrap();
+        fs::write(root.join("src/bin/extra.rs"), "").unwrap();
+        let before = snapshot(&dirs).unwrap();
+        assert_eq!(2, before.len());
+        assert!(changed(&before, &snapshot(&dirs).unwrap()).is_empty());
+
+        fs::create_dir_all(root.join("input")).unwrap();
+        fs::write(root.join("input/input.txt"), "1\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "// two, longer").unwrap();
+        fs::remove_file(root.join("src/bin/extra.rs")).unwrap();
+        assert_eq!(
+            vec![
+                root.join("input/input.txt"),
+                root.join("src/bin/extra.rs"),
+                root.join("src/lib.rs"),
+            ],
+            changed(&before, &snapshot(&dirs).unwrap())
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn can_diff_answers() {
+        let output = "day\tpart\tanswer\tparse_ns\tsolve_ns\n9\t1\t127\t10\t20\n9\t2\t62\t10\t30\n";
+        let current = parse_tsv_answers(output);
+        assert_eq!(vec![(1, "127".to_string()), (2, "62".to_string())], current);
+        assert_eq!(
+            vec!["part 1: 127 (new)", "part 2: 62 (new)"],
+            diff_answers(&[], &current)
+        );
+        let previous = vec![(1, "127".to_string()), (2, "61".to_string())];
+        assert_eq!(
+            vec!["part 1: 127 (unchanged)", "part 2: 62 (was 61)"],
+            diff_answers(&previous, &current)
+        );
+        assert_eq!(
+            vec!["part 1: 127 (unchanged)", "part 2: no answer (was 61)"],
+            diff_answers(&previous, &current[..1])
+        );
+    }
+}
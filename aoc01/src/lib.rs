@@ -0,0 +1,268 @@
+//! Day 1: finding the expense report entries that add up to 2020.
+
+use aoc_core::{aoc, int, parse, Error, Int, Result, Rng, Solution};
+use std::collections::HashSet;
+
+/// The puzzle, parsed as one `i64` entry per line.
+#[derive(Default)]
+pub struct Day01;
+
+#[aoc(day = 1)]
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Vec<i64>> {
+        parse::numbers(input)
+    }
+
+    fn part_one(&self, expenses: &Vec<i64>) -> Result<i64> {
+        let (lhs, rhs) = find_2sum(expenses, 2020)
+            .ok_or_else(|| Error::no_solution("no two entries sum to 2020"))?;
+        lhs.try_mul(rhs)
+    }
+
+    fn part_two(&self, expenses: &Vec<i64>) -> Result<i64> {
+        let (val1, val2, val3) = find_3sum(expenses, 2020)?
+            .ok_or_else(|| Error::no_solution("no three entries sum to 2020"))?;
+        int::product(vec![val1, val2, val3])
+    }
+
+    // Plants one pair and one triple summing to 2020. Every other entry is
+    // above 1010, so no two or three of them reach 2020 on their own, and
+    // values that would complete a sum with the planted entries are skipped.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let planted = loop {
+            let a = rng.range(1011, 2020) as i64;
+            let x = rng.range(1, 673) as i64;
+            let y = rng.range(1, 673) as i64;
+            let planted = vec![a, 2020 - a, x, y, 2020 - x - y];
+            if count_sums(&planted, 2, 2020) == 1 && count_sums(&planted, 3, 2020) == 1 {
+                break planted;
+            }
+        };
+
+        let mut taken: HashSet<i64> = planted.iter().cloned().collect();
+        for (pos, &p) in planted.iter().enumerate() {
+            taken.insert(2020 - p);
+            for &q in planted.iter().skip(pos + 1) {
+                taken.insert(2020 - p - q);
+            }
+        }
+        let mut expenses = planted;
+        while expenses.len() < size {
+            let filler = rng.range(1011, 2020) as i64;
+            if !taken.contains(&filler) {
+                expenses.push(filler);
+            }
+        }
+        rng.shuffle(&mut expenses);
+        Some(expenses.iter().map(|e| format!("{}\n", e)).collect())
+    }
+
+    fn explain(&self, expenses: &Vec<i64>, part: u8) -> Option<Result<String>> {
+        let entries = match part {
+            1 => find_2sum(expenses, 2020).map(|(a, b)| vec![a, b]),
+            _ => match find_3sum(expenses, 2020) {
+                Ok(triple) => triple.map(|(a, b, c)| vec![a, b, c]),
+                Err(e) => return Some(Err(e)),
+            },
+        }?;
+        let entries: Vec<String> = entries.iter().map(|e| e.to_string()).collect();
+        Some(Ok(format!(
+            "{} = 2020\nthe answer is {}",
+            entries.join(" + "),
+            entries.join(" * ")
+        )))
+    }
+}
+
+// How many ways `count` different entries add up to `sum`.
+fn count_sums(entries: &[i64], count: usize, sum: i64) -> usize {
+    match (count, entries.split_first()) {
+        (0, _) => (sum == 0) as usize,
+        (_, None) => 0,
+        (_, Some((&first, rest))) => {
+            count_sums(rest, count - 1, sum - first) + count_sums(rest, count, sum)
+        }
+    }
+}
+
+/// Two different entries that add up to `sum`.
+///
+/// A lone 1010 is not a solution for 2020 but two of them are. When
+/// `sum - val` does not fit in `T` no other entry can complete the pair, so
+/// that entry is simply skipped.
+pub fn find_2sum<T: Int>(input_array: &[T], sum: T) -> Option<(T, T)> {
+    let mut seen: HashSet<T> = HashSet::new();
+    for &val in input_array.iter() {
+        if let Some(target_val) = sum.checked_sub(val) {
+            if seen.contains(&target_val) {
+                return Some((target_val, val));
+            }
+        }
+        seen.insert(val);
+    }
+    None
+}
+
+/// Three different entries that add up to `sum`.
+///
+/// Two entries can still add up to a `sum - i` that does not fit in `T`, so
+/// that overflow is an error rather than a skipped entry.
+pub fn find_3sum<T: Int>(input_array: &[T], sum: T) -> Result<Option<(T, T, T)>> {
+    for (pos, &i) in input_array.iter().enumerate() {
+        if let Some((j, k)) = find_2sum(&input_array[pos + 1..], sum.try_sub(i)?) {
+            return Ok(Some((i, j, k)));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Rng;
+    use proptest::prelude::*;
+
+    const SAMPLE: &str = include_str!("../input/test.txt");
+
+    #[test]
+    fn can_explain_answers() {
+        let expenses = Day01.parse(SAMPLE).unwrap();
+        assert_eq!(
+            "1721 + 299 = 2020\nthe answer is 1721 * 299",
+            Day01.explain(&expenses, 1).unwrap().unwrap()
+        );
+        assert_eq!(
+            "979 + 366 + 675 = 2020\nthe answer is 979 * 366 * 675",
+            Day01.explain(&expenses, 2).unwrap().unwrap()
+        );
+        assert!(Day01.explain(&vec![1, 2], 1).is_none());
+    }
+
+    fn brute_force_2sum(input_array: &[i32], sum: i32) -> bool {
+        (0..input_array.len())
+            .any(|i| (i + 1..input_array.len()).any(|j| input_array[i] + input_array[j] == sum))
+    }
+
+    fn brute_force_3sum(input_array: &[i32], sum: i32) -> bool {
+        let n = input_array.len();
+        (0..n).any(|i| {
+            (i + 1..n).any(|j| {
+                (j + 1..n).any(|k| input_array[i] + input_array[j] + input_array[k] == sum)
+            })
+        })
+    }
+
+    // Removes each of `values` from `entries` once, failing if one is missing.
+    fn uses_entries<T: Int>(entries: &[T], values: &[T]) -> bool {
+        let mut remaining = entries.to_vec();
+        values
+            .iter()
+            .all(|v| match remaining.iter().position(|e| e == v) {
+                Some(pos) => {
+                    remaining.swap_remove(pos);
+                    true
+                }
+                None => false,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn find_2sum_matches_brute_force(
+            entries in prop::collection::vec(0..100i32, 0..20),
+            sum in 0..200i32,
+        ) {
+            let found = find_2sum(&entries, sum);
+            prop_assert_eq!(brute_force_2sum(&entries, sum), found.is_some());
+            if let Some((a, b)) = found {
+                prop_assert_eq!(sum, a + b);
+                prop_assert!(uses_entries(&entries, &[a, b]));
+            }
+        }
+
+        #[test]
+        fn find_3sum_matches_brute_force(
+            entries in prop::collection::vec(0..100i32, 0..20),
+            sum in 0..300i32,
+        ) {
+            let found = find_3sum(&entries, sum).unwrap();
+            prop_assert_eq!(brute_force_3sum(&entries, sum), found.is_some());
+            if let Some((a, b, c)) = found {
+                prop_assert_eq!(sum, a + b + c);
+                prop_assert!(uses_entries(&entries, &[a, b, c]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_part_1_sample_input() {
+        let result = Day01.part_one(&Day01.parse(SAMPLE).unwrap());
+        assert_eq!(514579, result.unwrap());
+    }
+
+    #[test]
+    fn test_part_2_sample_input() {
+        let result = Day01.part_two(&Day01.parse(SAMPLE).unwrap());
+        assert_eq!(241861950, result.unwrap());
+    }
+
+    #[test]
+    fn reports_bad_input() {
+        let error = Day01.parse("1721\n97x9\n").unwrap_err();
+        assert_eq!(Some(2), error.line);
+        assert_eq!(Some(1), error.column);
+        assert!(Day01.part_one(&vec![1, 2, 3]).is_err());
+        assert!(Day01.part_two(&vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn generated_input_has_one_pair_and_one_triple() {
+        let mut rng = Rng::new(1);
+        let input = Day01.generate(&mut rng, 60).unwrap();
+        let expenses = Day01.parse(&input).unwrap();
+        assert_eq!(60, expenses.len());
+        assert_eq!(1, count_sums(&expenses, 2, 2020));
+        assert_eq!(1, count_sums(&expenses, 3, 2020));
+        let (a, b) = find_2sum(&expenses, 2020).unwrap();
+        assert_eq!(a * b, Day01.part_one(&expenses).unwrap());
+        let (x, y, z) = find_3sum(&expenses, 2020).unwrap().unwrap();
+        assert_eq!(x * y * z, Day01.part_two(&expenses).unwrap());
+    }
+
+    #[test]
+    fn only_pairs_up_different_entries() {
+        assert_eq!(None, find_2sum(&[1010, 5], 2020));
+        assert_eq!(Some((1010, 1010)), find_2sum(&[1010, 5, 1010], 2020));
+        assert_eq!(None, find_3sum(&[1000, 10, 5], 2020).unwrap());
+    }
+
+    #[test]
+    fn works_in_any_width_without_overflowing() {
+        assert_eq!(Some((1, 5)), find_2sum(&[u64::MAX, 1, 5], 6));
+        // Wrapping arithmetic would pair these up.
+        assert_eq!(None, find_2sum(&[i32::MIN, -1], i32::MAX));
+        assert_eq!(
+            Some((1u128 << 100, 2, 3)),
+            find_3sum(&[7, 1 << 100, 2, 3], (1 << 100) + 5).unwrap()
+        );
+        assert!(find_3sum(&[i64::MIN, 1, 2], 2020).is_err());
+
+        let big = 4_000_000_000_000;
+        let error = Day01.part_one(&vec![big, 2020 - big]).unwrap_err();
+        assert!(
+            error.to_string().contains("does not fit in i64"),
+            "{}",
+            error
+        );
+        assert!(Day01.part_two(&vec![big, 2, 2018 - big]).is_err());
+        let fits = 1_000_000_000;
+        assert_eq!(
+            fits * 2 * (2018 - fits),
+            Day01.part_two(&vec![fits, 2, 2018 - fits]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Day 2: checking passwords against two readings of the same policies.
+
+use aoc_core::parse::{self, column_of, Spanned};
+use aoc_core::{aoc, int, Error, FromPattern, Int, Result, Rng, Solution};
+use std::str::FromStr;
+
+/// The puzzle, parsed as one policy and its password per line.
+#[derive(Default)]
+pub struct Day02;
+
+#[aoc(day = 2)]
+impl Solution for Day02 {
+    type Input = Vec<Policy>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<Policy>> {
+        parse::lines(input)
+            .map(|line| {
+                let text = line.text.trim();
+                // The policy's columns, counted from where it starts on the line.
+                let shift = column_of(line.text, text) - 1;
+                let mut policy = Policy::from_str(text).map_err(|mut e| {
+                    e.column = e.column.map(|c| c + shift);
+                    e.on_line(line.number, line.text)
+                })?;
+                policy.first.column += shift;
+                policy.second.column += shift;
+                Ok(policy)
+            })
+            .collect()
+    }
+
+    fn part_one(&self, policies: &Vec<Policy>) -> Result<i32> {
+        count_valid_passwords_part_1(policies)
+    }
+
+    fn part_two(&self, policies: &Vec<Policy>) -> Result<i32> {
+        count_valid_passwords_part_2(policies)
+    }
+
+    // A small alphabet so the policy letter actually turns up in passwords.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let letters: Vec<char> = ('a'..='f').collect();
+        let mut input = String::new();
+        for _ in 0..size {
+            let length = rng.range(1, 21);
+            let first = rng.range(1, length + 1);
+            let second = rng.range(first, length + 1);
+            let password: String = (0..length).map(|_| *rng.choose(&letters)).collect();
+            input.push_str(&format!(
+                "{}-{} {}: {}\n",
+                first,
+                second,
+                rng.choose(&letters),
+                password
+            ));
+        }
+        Some(input)
+    }
+}
+
+/// One line of the password database, such as `1-3 a: abcde`. The numbers
+/// keep their columns for errors about positions outside the password.
+#[derive(Debug, FromPattern)]
+#[pattern("{first}-{second} {letter}: {password}")]
+pub struct Policy {
+    pub first: Spanned<usize>,
+    pub second: Spanned<usize>,
+    pub letter: char,
+    pub password: String,
+}
+
+/// How many passwords follow their policy as the sled rental shop reads it.
+pub fn count_valid_passwords_part_1<T: Int>(policies: &[Policy]) -> Result<T> {
+    let mut validator: PasswordValidatorV1 = PasswordValidatorV1::new();
+    int::count(policies.iter().map(|policy| {
+        validator.load_policy(policy);
+        validator.is_password_valid()
+    }))
+}
+
+/// How many passwords follow their policy as the toboggan corporation reads
+/// it. Fails on a position outside its password.
+pub fn count_valid_passwords_part_2<T: Int>(policies: &[Policy]) -> Result<T> {
+    let mut validator: PasswordValidatorV2 = PasswordValidatorV2::new();
+    let mut valid = Vec::new();
+    for policy in policies.iter() {
+        validator.load_policy(policy)?;
+        valid.push(validator.is_password_valid());
+    }
+    int::count(valid)
+}
+
+/// The sled rental policy: `1-3 a` means `a` appears one to three times.
+pub struct PasswordValidatorV1 {
+    min_length: usize,
+    max_length: usize,
+    required_char: char,
+    password: String,
+}
+
+impl Default for PasswordValidatorV1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordValidatorV1 {
+    pub fn new() -> PasswordValidatorV1 {
+        PasswordValidatorV1 {
+            min_length: 0,
+            max_length: 0,
+            required_char: ' ',
+            password: String::new(),
+        }
+    }
+
+    /// Reads a `1-3 a: abcde` line to be checked next.
+    pub fn parse_policy_and_pw(&mut self, policy_and_pw: String) -> Result<()> {
+        let policy = Policy::from_str(&policy_and_pw).map_err(|e| e.with_text(&policy_and_pw))?;
+        self.load_policy(&policy);
+        Ok(())
+    }
+
+    /// Takes an already parsed policy to be checked next.
+    pub fn load_policy(&mut self, policy: &Policy) {
+        self.min_length = policy.first.value;
+        self.max_length = policy.second.value;
+        self.required_char = policy.letter;
+        self.password = policy.password.clone();
+    }
+
+    /// Whether the last line read follows its policy.
+    pub fn is_password_valid(&mut self) -> bool {
+        (self.min_length..=self.max_length)
+            .contains(&self.password.matches(self.required_char).count())
+    }
+}
+
+/// The toboggan policy: `1-3 a` means `a` is at exactly one of positions 1
+/// and 3, counting from one.
+pub struct PasswordValidatorV2 {
+    first_test_index: usize,
+    second_test_index: usize,
+    target_char: char,
+    password: String,
+}
+
+impl Default for PasswordValidatorV2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordValidatorV2 {
+    pub fn new() -> PasswordValidatorV2 {
+        PasswordValidatorV2 {
+            first_test_index: 0,
+            second_test_index: 0,
+            target_char: ' ',
+            password: String::new(),
+        }
+    }
+
+    /// Reads a `1-3 a: abcde` line to be checked next.
+    pub fn parse_policy_and_pw(&mut self, policy_and_pw: String) -> Result<()> {
+        let policy = Policy::from_str(&policy_and_pw).map_err(|e| e.with_text(&policy_and_pw))?;
+        self.load_policy(&policy)
+            .map_err(|e| e.with_text(&policy_and_pw))
+    }
+
+    /// Takes an already parsed policy to be checked next, as long as both of
+    /// its positions are inside the password.
+    pub fn load_policy(&mut self, policy: &Policy) -> Result<()> {
+        let password_length = policy.password.chars().count();
+        for position in [policy.first, policy.second].iter() {
+            if position.value == 0 || position.value > password_length {
+                return Err(Error::parse(format!(
+                    "position {} is outside the {} character password `{}`",
+                    position.value, password_length, policy.password
+                ))
+                .at_column(position.column));
+            }
+        }
+
+        self.first_test_index = policy.first.value - 1;
+        self.second_test_index = policy.second.value - 1;
+        self.target_char = policy.letter;
+        self.password = policy.password.clone();
+        Ok(())
+    }
+
+    /// Whether the last line read follows its policy.
+    pub fn is_password_valid(&mut self) -> bool {
+        let password_chars: Vec<char> = self.password.chars().collect();
+        let first_matches = password_chars.get(self.first_test_index) == Some(&self.target_char);
+        let second_matches = password_chars.get(self.second_test_index) == Some(&self.target_char);
+
+        first_matches != second_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_input_matches_policies() {
+        let input = Day02.generate(&mut Rng::new(2), 200).unwrap();
+        let policies = Day02.parse(&input).unwrap();
+        let (mut old_policy, mut new_policy) = (0, 0);
+        for policy in policies.iter() {
+            let (first, second) = (policy.first.value, policy.second.value);
+            let letter = policy.letter;
+            let count = policy.password.chars().filter(|&c| c == letter).count();
+            if (first..=second).contains(&count) {
+                old_policy += 1;
+            }
+            let at = |pos: usize| policy.password.chars().nth(pos - 1) == Some(letter);
+            if at(first) != at(second) {
+                new_policy += 1;
+            }
+        }
+        assert_eq!(200, policies.len());
+        assert_eq!(old_policy, Day02.part_one(&policies).unwrap());
+        assert_eq!(new_policy, Day02.part_two(&policies).unwrap());
+    }
+
+    #[test]
+    fn can_parse_policy_and_pw_part_1() {
+        let mut validator: PasswordValidatorV1 = PasswordValidatorV1::new();
+        validator
+            .parse_policy_and_pw("1-3 a: aabbcc".to_string())
+            .unwrap();
+
+        assert_eq!(1, validator.min_length);
+        assert_eq!(3, validator.max_length);
+        assert_eq!('a', validator.required_char);
+        assert_eq!("aabbcc", validator.password);
+    }
+
+    #[test]
+    fn can_validate_password_part_1() {
+        let mut validator: PasswordValidatorV1 = PasswordValidatorV1::new();
+        validator
+            .parse_policy_and_pw("1-3 a: aabbcc".to_string())
+            .unwrap();
+        assert!(validator.is_password_valid());
+        validator
+            .parse_policy_and_pw("1-3 b: cdefg".to_string())
+            .unwrap();
+        assert!(!validator.is_password_valid());
+        validator
+            .parse_policy_and_pw("2-9 c: ccccccccc".to_string())
+            .unwrap();
+        assert!(validator.is_password_valid());
+    }
+
+    #[test]
+    fn can_parse_policy_and_pw_part_2() {
+        let mut validator: PasswordValidatorV2 = PasswordValidatorV2::new();
+        validator
+            .parse_policy_and_pw("1-3 a: aabbcc".to_string())
+            .unwrap();
+
+        assert_eq!(0, validator.first_test_index);
+        assert_eq!(2, validator.second_test_index);
+        assert_eq!('a', validator.target_char);
+        assert_eq!("aabbcc", validator.password);
+    }
+
+    #[test]
+    fn can_validate_password_part_2() {
+        let mut validator: PasswordValidatorV2 = PasswordValidatorV2::new();
+        validator
+            .parse_policy_and_pw("1-3 a: aabbcc".to_string())
+            .unwrap();
+        assert!(validator.is_password_valid());
+        validator
+            .parse_policy_and_pw("1-3 b: cdefg".to_string())
+            .unwrap();
+        assert!(!validator.is_password_valid());
+        validator
+            .parse_policy_and_pw("2-9 c: ccccccccc".to_string())
+            .unwrap();
+        assert!(!validator.is_password_valid());
+    }
+
+    #[test]
+    fn can_count_valid_passwords() {
+        let policies_and_pws = Day02
+            .parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n")
+            .unwrap();
+        assert_eq!(2, Day02.part_one(&policies_and_pws).unwrap());
+        assert_eq!(1, Day02.part_two(&policies_and_pws).unwrap());
+        assert_eq!(
+            2u64,
+            count_valid_passwords_part_1(&policies_and_pws).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_count_crlf_and_indented_policies() {
+        let input = "  1-3 a: abcde\r\n1-3 b: cdefg  \r\n\t2-9 c: ccccccccc\r\n";
+        let policies = Day02.parse(input).unwrap();
+        assert_eq!("abcde", policies[0].password);
+        assert_eq!(2, Day02.part_one(&policies).unwrap());
+        assert_eq!(1, Day02.part_two(&policies).unwrap());
+        let error = Day02.parse("1-3 a: abc\r\n  1-x a: abc\r\n").unwrap_err();
+        assert_eq!(Some(2), error.line);
+        assert_eq!(Some(5), error.column);
+        let policies = Day02.parse("  2-12 a: abc\n").unwrap();
+        assert_eq!(5, policies[0].second.column);
+    }
+
+    #[test]
+    fn reports_bad_policies() {
+        let mut validator: PasswordValidatorV1 = PasswordValidatorV1::new();
+        let error = validator
+            .parse_policy_and_pw("1-x a: abc".to_string())
+            .unwrap_err();
+        assert_eq!(Some(3), error.column);
+        assert_eq!(Some("1-x a: abc".to_string()), error.text);
+        let error = validator
+            .parse_policy_and_pw("1-3 ab: abc".to_string())
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("column 5: `letter` cannot be `ab` (too many characters"));
+        let error = validator
+            .parse_policy_and_pw("1-3 a".to_string())
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("column 6: expected `: ` after `letter`"));
+
+        let mut validator: PasswordValidatorV2 = PasswordValidatorV2::new();
+        let error = validator
+            .parse_policy_and_pw("2-12 a: abc".to_string())
+            .unwrap_err();
+        assert_eq!(Some(3), error.column);
+        let error = validator
+            .parse_policy_and_pw("01-12 a: abc".to_string())
+            .unwrap_err();
+        assert_eq!(Some(4), error.column);
+        assert!(validator
+            .parse_policy_and_pw("0-1 a: abc".to_string())
+            .is_err());
+
+        let error = Day02.parse("1-3 a: abcde\n\n1-3 b:\n").unwrap_err();
+        assert_eq!(Some(3), error.line);
+        let policies = Day02.parse("1-3 a: abc\n2-12 a: abc\n").unwrap();
+        assert_eq!(1, Day02.part_one(&policies).unwrap());
+        assert!(Day02.part_two(&policies).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Day 3: counting the trees on a toboggan run down a repeating map.
+
+use aoc_core::repl::argument;
+use aoc_core::trace::Value;
+use aoc_core::{aoc, int, parse, trace, Error, Int, Result, Rng, Solution};
+
+/// The puzzle, parsed as rows of open squares (`.`) and trees (`#`).
+#[derive(Default)]
+pub struct Day03;
+
+#[aoc(day = 3)]
+impl Solution for Day03 {
+    type Input = Vec<Vec<char>>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<char>>> {
+        let map_grid = parse::grid(input, ".#")?;
+        match map_grid.first() {
+            Some(first_row) if !first_row.is_empty() => Ok(map_grid),
+            _ => Err(Error::parse("the map is empty")),
+        }
+    }
+
+    fn part_one(&self, ski_map: &Vec<Vec<char>>) -> Result<u64> {
+        traverse_slope(ski_map, 3, 1)
+    }
+
+    fn part_two(&self, ski_map: &Vec<Vec<char>>) -> Result<u64> {
+        int::product(vec![
+            traverse_slope(ski_map, 1, 1)?,
+            traverse_slope(ski_map, 3, 1)?,
+            traverse_slope(ski_map, 5, 1)?,
+            traverse_slope(ski_map, 7, 1)?,
+            traverse_slope(ski_map, 1, 2)?,
+        ])
+    }
+
+    // As wide as the real map, with `size` rows and about a quarter trees.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut ski_map = String::new();
+        for _ in 0..size.max(1) {
+            for _ in 0..31 {
+                ski_map.push(if rng.chance(1, 4) { '#' } else { '.' });
+            }
+            ski_map.push('\n');
+        }
+        Some(ski_map)
+    }
+
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("slope <right> <down>", "count the trees on a slope")]
+    }
+
+    fn explore(&self, ski_map: &Vec<Vec<char>>, words: &[&str]) -> Option<Result<String>> {
+        match words[0] {
+            "slope" => Some(explore_slope(ski_map, words)),
+            _ => None,
+        }
+    }
+}
+
+fn explore_slope(ski_map: &[Vec<char>], words: &[&str]) -> Result<String> {
+    let right = argument(words, 1, "step right")?;
+    let down = argument(words, 2, "step down")?;
+    Ok(format!(
+        "{} trees",
+        traverse_slope::<u64>(ski_map, right, down)?
+    ))
+}
+
+/// The trees (`#`) hit going `x_inc` right and `y_inc` down from the top left
+/// until the bottom, with the map repeating to the right.
+pub fn traverse_slope<T: Int>(ski_map: &[Vec<char>], x_inc: usize, y_inc: usize) -> Result<T> {
+    let mut x: usize = 0;
+    let mut y: usize = 0;
+    let mut tree_count = T::from(0);
+    if y_inc == 0 {
+        return Err(Error::no_solution(
+            "a slope has to go down to ever reach the bottom",
+        ));
+    }
+    let y_max: usize = ski_map.len();
+    let x_max: usize = match ski_map.first() {
+        Some(first_row) if !first_row.is_empty() => first_row.len(),
+        _ => return Err(Error::parse("the map is empty")),
+    };
+    while y < y_max {
+        let tree = ski_map[y][x] == '#';
+        trace!(Debug, "step", x = x, y = y, tree = tree);
+        if tree {
+            tree_count = tree_count.try_add(T::from(1))?;
+        }
+        x = (x + x_inc) % x_max;
+        y += y_inc;
+    }
+    trace!(
+        Info,
+        "slope",
+        right = x_inc,
+        down = y_inc,
+        trees = Value::number(tree_count)
+    );
+    Ok(tree_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../input/test.txt");
+
+    #[test]
+    fn can_explore_slopes() {
+        let day = Day03.parse(SAMPLE).unwrap();
+        let slope = |words: &[&str]| Day03.explore(&day, words).unwrap();
+        assert_eq!("7 trees", slope(&["slope", "3", "1"]).unwrap());
+        assert!(slope(&["slope", "3"]).is_err());
+        assert!(slope(&["slope", "3", "0"]).is_err());
+        assert!(Day03.explore(&day, &["sled"]).is_none());
+    }
+
+    #[test]
+    fn can_traverse_slope() {
+        let ski_map = Day03.parse(SAMPLE).unwrap();
+        for &(x_inc, y_inc, trees) in &[(3, 1, 7), (1, 1, 2), (5, 1, 3), (7, 1, 4), (1, 2, 2)] {
+            assert_eq!(
+                trees,
+                traverse_slope::<u64>(&ski_map, x_inc, y_inc).unwrap()
+            );
+        }
+        assert_eq!(7i128, traverse_slope(&ski_map, 3, 1).unwrap());
+    }
+
+    #[test]
+    fn generated_map_matches_tree_count() {
+        let ski_map = Day03.generate(&mut Rng::new(3), 500).unwrap();
+        let rows: Vec<&[u8]> = ski_map.lines().map(str::as_bytes).collect();
+        assert_eq!(500, rows.len());
+        let trees = (0..rows.len())
+            .filter(|&y| rows[y][(y * 3) % rows[y].len()] == b'#')
+            .count() as u64;
+        let ski_map = Day03.parse(&ski_map).unwrap();
+        assert_eq!(trees, Day03.part_one(&ski_map).unwrap());
+        assert!(Day03.part_two(&ski_map).is_ok());
+    }
+
+    #[test]
+    fn reports_bad_maps() {
+        assert!(Day03.parse("").is_err());
+        assert!(traverse_slope::<u64>(&[], 3, 1).is_err());
+        let ski_map = Day03.parse("..#\n.#.\n").unwrap();
+        assert!(traverse_slope::<u64>(&ski_map, 3, 0).is_err());
+        let error = Day03.parse("..#\n.#.\n..").unwrap_err();
+        assert_eq!(Some(3), error.line);
+        let error = Day03.parse("..#\n.O.\n").unwrap_err();
+        assert_eq!(Some(2), error.line);
+        assert_eq!(Some(2), error.column);
+    }
+}
@@ -0,0 +1,545 @@
+//! Day 4: checking passport batch files for required and valid fields.
+
+use aoc_core::parse::{self, column_of};
+use aoc_core::repl::argument;
+use aoc_core::{aoc, int, Error, Int, Result, Rng, Solution};
+use regex::Captures;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// A passport's fields, such as `byr` or `hgt`, and their values.
+pub type Passport = HashMap<String, String>;
+
+/// The puzzle, parsed as a list of passports.
+#[derive(Default)]
+pub struct Day04;
+
+#[aoc(day = 4)]
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<Passport>> {
+        parse_passport_batch_file(input)
+    }
+
+    fn part_one(&self, passports: &Vec<Passport>) -> Result<i32> {
+        count_valid_passports_part_1(passports)
+    }
+
+    fn part_two(&self, passports: &Vec<Passport>) -> Result<i32> {
+        count_valid_passports_part_2(passports)
+    }
+
+    // Follows the puzzle's rules: most fields are there and most values are
+    // valid, but every kind of missing field and bad value turns up.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_batch(rng, size).0)
+    }
+
+    // Part 1 only cares about missing fields, part 2 about bad values too.
+    fn explain(&self, passports: &Vec<Passport>, part: u8) -> Option<Result<String>> {
+        let mut lines = Vec::new();
+        for (idx, passport) in passports.iter().enumerate() {
+            let problems = passport_problems(passport, part == 2);
+            if !problems.is_empty() {
+                lines.push(format!("passport {}: {}", idx + 1, problems.join(", ")));
+            }
+        }
+        lines.push(format!(
+            "{} of {} passports are invalid",
+            lines.len(),
+            passports.len()
+        ));
+        Some(Ok(lines.join("\n")))
+    }
+
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "show passport <n>",
+                "show the nth passport, counting from 1",
+            ),
+            ("validate <n>", "check each field of the nth passport"),
+        ]
+    }
+
+    fn explore(&self, passports: &Vec<Passport>, words: &[&str]) -> Option<Result<String>> {
+        match words[0] {
+            "show" if words.get(1) == Some(&"passport") => {
+                Some(passport_at(passports, words, 2).map(show_passport))
+            }
+            "validate" => Some(passport_at(passports, words, 1).map(validate_passport)),
+            _ => None,
+        }
+    }
+}
+
+fn passport_at<'a>(passports: &'a [Passport], words: &[&str], idx: usize) -> Result<&'a Passport> {
+    let number: usize = argument(words, idx, "passport number")?;
+    match number.checked_sub(1).and_then(|idx| passports.get(idx)) {
+        Some(passport) => Ok(passport),
+        None => Err(Error::parse(format!(
+            "there are passports 1 to {}, not {}",
+            passports.len(),
+            number
+        ))),
+    }
+}
+
+fn passport_problems(passport: &Passport, check_values: bool) -> Vec<String> {
+    FIELD_CHECKS
+        .iter()
+        .filter_map(|(field, check)| match passport.get(*field) {
+            None => Some(format!("{} missing", field)),
+            Some(value) if check_values && !check(value.to_string()) => {
+                Some(format!("{} {} invalid", field, value))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn show_passport(passport: &Passport) -> String {
+    let mut fields: Vec<String> = passport
+        .iter()
+        .map(|(field, value)| format!("{}:{}", field, value))
+        .collect();
+    fields.sort();
+    fields.join(" ")
+}
+
+fn validate_passport(passport: &Passport) -> String {
+    let mut lines: Vec<String> = FIELD_CHECKS
+        .iter()
+        .map(|(field, check)| match passport.get(*field) {
+            Some(value) if check(value.to_string()) => format!("{} {} ok", field, value),
+            Some(value) => format!("{} {} invalid", field, value),
+            None => format!("{} missing", field),
+        })
+        .collect();
+    lines.push(
+        if has_valid_passport(passport) {
+            "valid"
+        } else if has_passport_fields(passport) {
+            "has every field, but not valid"
+        } else {
+            "invalid"
+        }
+        .to_string(),
+    );
+    lines.join("\n")
+}
+
+/// A batch of `size` passports and how many of them are valid for part 2.
+fn generate_batch(rng: &mut Rng, size: usize) -> (String, usize) {
+    let mut batches = Vec::new();
+    let mut planted = 0;
+    for _ in 0..size {
+        let mut fields = Vec::new();
+        let mut valid_passport = true;
+        for &field in ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"].iter() {
+            let wanted = if field == "cid" { (1, 2) } else { (9, 10) };
+            if rng.chance(wanted.0, wanted.1) {
+                let valid = rng.chance(3, 4);
+                valid_passport &= valid || field == "cid";
+                fields.push(format!("{}:{}", field, generate_value(rng, field, valid)));
+            } else if field != "cid" {
+                valid_passport = false;
+            }
+        }
+        if valid_passport {
+            planted += 1;
+        }
+        if fields.is_empty() {
+            fields.push(format!("cid:{}", generate_value(rng, "cid", true)));
+        }
+        rng.shuffle(&mut fields);
+        let mut passport = String::new();
+        for (idx, field) in fields.iter().enumerate() {
+            if idx > 0 {
+                passport.push(if rng.chance(1, 3) { '\n' } else { ' ' });
+            }
+            passport.push_str(field);
+        }
+        batches.push(passport);
+    }
+    (batches.join("\n\n") + "\n", planted)
+}
+
+fn generate_value(rng: &mut Rng, field: &str, valid: bool) -> String {
+    let year = |rng: &mut Rng, low: u64, high: u64| {
+        if valid {
+            rng.range(low, high + 1)
+        } else if rng.chance(1, 2) {
+            rng.range(low - 20, low)
+        } else {
+            rng.range(high + 1, high + 20)
+        }
+    };
+    let hex = |rng: &mut Rng| -> String {
+        (0..6)
+            .map(|_| *rng.choose(b"0123456789abcdef") as char)
+            .collect()
+    };
+    match field {
+        "byr" => year(rng, 1920, 2002).to_string(),
+        "iyr" => year(rng, 2010, 2020).to_string(),
+        "eyr" => year(rng, 2020, 2030).to_string(),
+        "hgt" => match (valid, rng.chance(1, 2)) {
+            (true, true) => format!("{}cm", rng.range(150, 194)),
+            (true, false) => format!("{}in", rng.range(59, 77)),
+            (false, true) => format!("{}cm", rng.range(100, 150)),
+            (false, false) => rng.range(50, 200).to_string(),
+        },
+        "hcl" if valid => format!("#{}", hex(rng)),
+        "hcl" => hex(rng),
+        "ecl" if valid => rng
+            .choose(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])
+            .to_string(),
+        "ecl" => rng.choose(&["red", "xry", "zzz"]).to_string(),
+        "pid" => {
+            let digits = if valid { 9 } else { *rng.choose(&[8, 10]) };
+            (0..digits).map(|_| rng.range(0, 10).to_string()).collect()
+        }
+        _ => rng.range(100, 1000).to_string(),
+    }
+}
+
+/// Reads passports written as runs of `field:value` pairs separated by blank
+/// lines.
+pub fn parse_passport_batch_file(input: &str) -> Result<Vec<Passport>> {
+    let mut passports: Vec<Passport> = Vec::new();
+
+    for group in parse::groups(input) {
+        let mut passport_entry = Passport::new();
+        for line in group {
+            for pair in line.text.split_whitespace() {
+                match pair.find(':') {
+                    Some(colon) if colon > 0 => {
+                        passport_entry
+                            .insert(pair[..colon].to_string(), pair[colon + 1..].to_string());
+                    }
+                    _ => {
+                        return Err(Error::parse(format!(
+                            "expected `field:value`, found `{}`",
+                            pair
+                        ))
+                        .at_column(column_of(line.text, pair))
+                        .on_line(line.number, line.text))
+                    }
+                }
+            }
+        }
+        passports.push(passport_entry);
+    }
+
+    Ok(passports)
+}
+
+/// How many passports have every required field; `cid` is optional.
+pub fn count_valid_passports_part_1<T: Int>(passports: &[Passport]) -> Result<T> {
+    int::count(passports.iter().map(has_passport_fields))
+}
+
+/// How many passports have every required field with a valid value.
+pub fn count_valid_passports_part_2<T: Int>(passports: &[Passport]) -> Result<T> {
+    int::count(passports.iter().map(has_valid_passport))
+}
+
+/// Whether every field but `cid` is present.
+pub fn has_passport_fields(passport: &Passport) -> bool {
+    passport.contains_key("byr")
+        && passport.contains_key("iyr")
+        && passport.contains_key("eyr")
+        && passport.contains_key("hgt")
+        && passport.contains_key("hcl")
+        && passport.contains_key("ecl")
+        && passport.contains_key("pid")
+}
+
+/// Whether every field but `cid` is present and valid.
+///
+/// A field that is present but malformed makes the passport invalid rather
+/// than the batch file unreadable.
+pub fn has_valid_passport(passport: &Passport) -> bool {
+    let field_is = |field: &str, check: FieldCheck| {
+        passport
+            .get(field)
+            .is_some_and(|value| check(value.to_string()))
+    };
+
+    has_passport_fields(passport)
+        && FIELD_CHECKS
+            .iter()
+            .all(|&(field, check)| field_is(field, check))
+}
+
+type FieldCheck = fn(String) -> bool;
+
+const FIELD_CHECKS: [(&str, FieldCheck); 7] = [
+    ("byr", has_valid_birth_year),
+    ("iyr", has_valid_issue_year),
+    ("eyr", has_valid_expiration_year),
+    ("hgt", has_valid_height),
+    ("ecl", has_valid_eye_color),
+    ("hcl", has_valid_hair_color),
+    ("pid", has_valid_passport_id),
+];
+
+// byr (Birth Year) - four digits; at least 1920 and at most 2002.
+fn has_valid_birth_year(birth_year: String) -> bool {
+    matches!(birth_year.parse::<i32>(), Ok(year) if (1920..=2002).contains(&year))
+}
+
+// iyr (Issue Year) - four digits; at least 2010 and at most 2020.
+fn has_valid_issue_year(issue_year: String) -> bool {
+    matches!(issue_year.parse::<i32>(), Ok(year) if (2010..=2020).contains(&year))
+}
+
+// eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
+fn has_valid_expiration_year(expiration_year: String) -> bool {
+    matches!(expiration_year.parse::<i32>(), Ok(year) if (2020..=2030).contains(&year))
+}
+
+/*
+hgt (Height) - a number followed by either cm or in:
+
+    If cm, the number must be at least 150 and at most 193.
+    If in, the number must be at least 59 and at most 76.
+*/
+fn has_valid_height(height: String) -> bool {
+    let re: Regex = Regex::new(r"(\d+)((in|cm)?)").unwrap();
+    let c: Captures = match re.captures(&height) {
+        Some(c) => c,
+        None => return false,
+    };
+    let height_value: i32 = match c[1].parse::<i32>() {
+        Ok(height_value) => height_value,
+        Err(_) => return false,
+    };
+    let unit: &str = &c[2];
+
+    if unit == "cm" {
+        (150..=193).contains(&height_value)
+    } else if unit == "in" {
+        (59..=76).contains(&height_value)
+    } else {
+        false
+    }
+}
+
+// ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
+fn has_valid_eye_color(eye_color: String) -> bool {
+    ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
+        .iter()
+        .any(|&eye| eye == eye_color)
+}
+
+// hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
+fn has_valid_hair_color(hair_color: String) -> bool {
+    Regex::new(r"#[0-9a-f]{6}").unwrap().is_match(&hair_color)
+}
+
+// pid (Passport ID) - a nine-digit number, including leading zeroes.
+fn has_valid_passport_id(passport_id: String) -> bool {
+    passport_id.chars().count() == 9
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../input/test.txt");
+
+    #[test]
+    fn can_explain_invalid_passports() {
+        let passports = Day04.parse(SAMPLE).unwrap();
+        assert_eq!(
+            "passport 2: hgt missing\npassport 4: byr missing\n2 of 4 passports are invalid",
+            Day04.explain(&passports, 1).unwrap().unwrap()
+        );
+        let explanation = Day04.explain(&passports, 2).unwrap().unwrap();
+        assert!(
+            explanation.ends_with("\n2 of 4 passports are invalid"),
+            "{}",
+            explanation
+        );
+        for passport in passports.iter() {
+            assert_eq!(
+                has_valid_passport(passport),
+                passport_problems(passport, true).is_empty()
+            );
+        }
+    }
+
+    #[test]
+    fn can_explore_passports() {
+        let passports = Day04.parse(SAMPLE).unwrap();
+        let explore = |words: &[&str]| Day04.explore(&passports, words).unwrap();
+        assert_eq!(
+            "byr:1931 ecl:brn eyr:2024 hcl:#ae17e1 hgt:179cm iyr:2013 pid:760753108",
+            explore(&["show", "passport", "3"]).unwrap()
+        );
+        let report = explore(&["validate", "2"]).unwrap();
+        assert!(report.contains("\nhgt missing\n"), "{}", report);
+        assert!(report.ends_with("\ninvalid"), "{}", report);
+        assert!(explore(&["validate", "0"]).is_err());
+        assert!(explore(&["validate", "5"]).is_err());
+        assert!(Day04.explore(&passports, &["show", "3"]).is_none());
+    }
+
+    #[test]
+    fn can_parse_batch_file() {
+        let passport_list = parse_passport_batch_file(SAMPLE).unwrap();
+        assert_eq!(4, passport_list.len());
+
+        let passport = &passport_list[2];
+
+        assert_eq!("2024", passport.get("eyr").unwrap());
+        assert_eq!(None, passport.get("cid"));
+    }
+
+    #[test]
+    fn can_check_passport_fields() {
+        let passport_list = parse_passport_batch_file(SAMPLE).unwrap();
+        assert!(has_passport_fields(&passport_list[0]));
+        assert!(!has_passport_fields(&passport_list[1]));
+        assert!(has_passport_fields(&passport_list[2]));
+    }
+
+    #[test]
+    fn can_validate_birth_year() {
+        let valid_birth_year = &parse_passport_batch_file("byr:1937").unwrap()[0];
+        assert!(has_valid_birth_year(
+            valid_birth_year.get("byr").unwrap().to_string()
+        ));
+        let invalid_birth_year = &parse_passport_batch_file("byr:1900").unwrap()[0];
+        assert!(!has_valid_birth_year(
+            invalid_birth_year.get("byr").unwrap().to_string()
+        ));
+    }
+
+    #[test]
+    fn can_validate_issue_year() {
+        let valid_issue_year = &parse_passport_batch_file("iyr:2015").unwrap()[0];
+        assert!(has_valid_issue_year(
+            valid_issue_year.get("iyr").unwrap().to_string()
+        ));
+        let invalid_issue_year = &parse_passport_batch_file("iyr:2001").unwrap()[0];
+        assert!(!has_valid_issue_year(
+            invalid_issue_year.get("iyr").unwrap().to_string()
+        ));
+    }
+
+    #[test]
+    fn can_validate_expiration_year() {
+        let valid_expiration_year = &parse_passport_batch_file("eyr:2022").unwrap()[0];
+        assert!(has_valid_expiration_year(
+            valid_expiration_year.get("eyr").unwrap().to_string()
+        ));
+        let invalid_expiration_year = &parse_passport_batch_file("eyr:2049").unwrap()[0];
+        assert!(!has_valid_expiration_year(
+            invalid_expiration_year.get("eyr").unwrap().to_string()
+        ));
+    }
+
+    #[test]
+    fn can_validate_valid_height() {
+        let valid_height_inches = &parse_passport_batch_file("hgt:60in").unwrap()[0];
+        assert!(has_valid_height(
+            valid_height_inches.get("hgt").unwrap().to_string()
+        ));
+        let invalid_height_inches = &parse_passport_batch_file("hgt:100in").unwrap()[0];
+        assert!(!has_valid_height(
+            invalid_height_inches.get("hgt").unwrap().to_string()
+        ));
+        let valid_height_centimeters = &parse_passport_batch_file("hgt:190cm").unwrap()[0];
+        assert!(has_valid_height(
+            valid_height_centimeters.get("hgt").unwrap().to_string()
+        ));
+        let invalid_height_centimeters = &parse_passport_batch_file("hgt:80cm").unwrap()[0];
+        assert!(!has_valid_height(
+            invalid_height_centimeters.get("hgt").unwrap().to_string()
+        ));
+        let invalid_height_no_units = &parse_passport_batch_file("hgt:999").unwrap()[0];
+        assert!(!has_valid_height(
+            invalid_height_no_units.get("hgt").unwrap().to_string()
+        ));
+    }
+
+    #[test]
+    fn can_validate_eye_color() {
+        let valid_eye_color = &parse_passport_batch_file("ecl:brn").unwrap()[0];
+        assert!(has_valid_eye_color(
+            valid_eye_color.get("ecl").unwrap().to_string()
+        ));
+        let invalid_eye_color = &parse_passport_batch_file("ecl:red").unwrap()[0];
+        assert!(!has_valid_eye_color(
+            invalid_eye_color.get("ecl").unwrap().to_string()
+        ));
+    }
+
+    #[test]
+    fn can_validate_hair_color() {
+        let valid_hair_color = &parse_passport_batch_file("hcl:#112233").unwrap()[0];
+        assert!(has_valid_hair_color(
+            valid_hair_color.get("hcl").unwrap().to_string()
+        ));
+        let invalid_hair_color = &parse_passport_batch_file("hcl:rgb(50,100,150)").unwrap()[0];
+        assert!(!has_valid_hair_color(
+            invalid_hair_color.get("hcl").unwrap().to_string()
+        ));
+    }
+
+    #[test]
+    fn can_validate_passport_id() {
+        let valid_passport_id = &parse_passport_batch_file("pid:012345678").unwrap()[0];
+        assert!(has_valid_passport_id(
+            valid_passport_id.get("pid").unwrap().to_string()
+        ));
+        let invalid_passport_id = &parse_passport_batch_file("pid:393939").unwrap()[0];
+        assert!(!has_valid_passport_id(
+            invalid_passport_id.get("pid").unwrap().to_string()
+        ));
+    }
+
+    #[test]
+    fn generated_batch_has_every_passport() {
+        let (input, planted) = generate_batch(&mut Rng::new(4), 300);
+        let passports = Day04.parse(&input).unwrap();
+        assert_eq!(300, passports.len());
+        let complete = input
+            .split("\n\n")
+            .filter(|batch| {
+                ["byr:", "iyr:", "eyr:", "hgt:", "hcl:", "ecl:", "pid:"]
+                    .iter()
+                    .all(|field| batch.contains(field))
+            })
+            .count() as i32;
+        assert_eq!(complete, Day04.part_one(&passports).unwrap());
+        assert!(planted > 0);
+        assert_eq!(planted as i32, Day04.part_two(&passports).unwrap());
+    }
+
+    #[test]
+    fn reports_bad_batch_file() {
+        let error =
+            parse_passport_batch_file("ecl:gry pid:860033327\n\nbyr:1937 iyr 2017").unwrap_err();
+        assert_eq!(Some(3), error.line);
+        assert_eq!(Some(10), error.column);
+    }
+
+    #[test]
+    fn treats_malformed_values_as_invalid() {
+        let passport = &parse_passport_batch_file(
+            "byr:19x7 iyr:2017 eyr:2020 hgt:cm hcl:#fffffd ecl:gry pid:860033327",
+        )
+        .unwrap()[0];
+        assert!(has_passport_fields(passport));
+        assert!(!has_valid_passport(passport));
+        assert!(!has_valid_height("tall".to_string()));
+        assert!(!has_valid_height("99999999999in".to_string()));
+    }
+}
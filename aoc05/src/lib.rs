@@ -0,0 +1,328 @@
+//! Day 5: decoding binary space partitioned boarding passes.
+
+use aoc_core::parse::{self, column_of};
+use aoc_core::{aoc, trace, Error, Int, Result, Rng, Solution};
+use std::cmp;
+
+/// The puzzle, parsed as the seat ID of every boarding pass.
+#[derive(Default)]
+pub struct Day05;
+
+#[aoc(day = 5)]
+impl Solution for Day05 {
+    type Input = Vec<i32>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>> {
+        parse::lines(input)
+            .map(|line| {
+                let pass = line.text.trim();
+                find_seat_id(pass).map_err(|mut e| {
+                    // The pass's columns, counted from where it starts on the line.
+                    e.column = e.column.map(|c| c + column_of(line.text, pass) - 1);
+                    e.on_line(line.number, line.text)
+                })
+            })
+            .collect()
+    }
+
+    fn part_one(&self, seat_ids: &Vec<i32>) -> Result<i32> {
+        let mut max_id = -1;
+
+        for &seat_id in seat_ids {
+            max_id = cmp::max(max_id, seat_id);
+        }
+        if max_id < 0 {
+            return Err(Error::no_solution("there are no boarding passes"));
+        }
+        Ok(max_id)
+    }
+
+    // Seats at the very front and back do not exist, so ours is the only
+    // missing id with both neighbours taken.
+    fn part_two(&self, seat_ids: &Vec<i32>) -> Result<i32> {
+        let mut seats: Vec<i32> = seat_ids.clone();
+
+        seats.sort();
+        seats.dedup();
+        for pair in seats.windows(2) {
+            if pair[1] - pair[0] == 2 {
+                return Ok(pair[0] + 1);
+            }
+        }
+        Err(Error::no_solution(
+            "there is no gap between the occupied seats",
+        ))
+    }
+
+    // A run of occupied seats somewhere on the plane with exactly one empty
+    // seat inside it. There are only 1024 seats, so `size` tops out there.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let seats = size.clamp(2, 1023) as u64 + 1;
+        let first = rng.range(0, 1024 - seats + 1) as i32;
+        let missing = first + 1 + rng.below(seats as usize - 2) as i32;
+        let mut passes: Vec<String> = (first..first + seats as i32)
+            .filter(|&id| id != missing)
+            .map(boarding_pass_for)
+            .collect();
+        rng.shuffle(&mut passes);
+        Some(passes.join("\n") + "\n")
+    }
+
+    fn explain(&self, seat_ids: &Vec<i32>, part: u8) -> Option<Result<String>> {
+        let answer = match part {
+            1 => self.part_one(seat_ids),
+            _ => self.part_two(seat_ids),
+        };
+        let seat_id = match answer {
+            Ok(seat_id) => seat_id,
+            Err(e) => return Some(Err(e)),
+        };
+        let seat = format!(
+            "seat {} is row {}, column {} ({})",
+            seat_id,
+            seat_id / 8,
+            seat_id % 8,
+            boarding_pass_for(seat_id)
+        );
+        Some(Ok(match part {
+            1 => format!("{}, the highest of {} passes", seat, seat_ids.len()),
+            _ => format!(
+                "{}, free between the taken seats {} and {}",
+                seat,
+                seat_id - 1,
+                seat_id + 1
+            ),
+        }))
+    }
+}
+
+/// The boarding pass that decodes to `seat_id`.
+pub fn boarding_pass_for(seat_id: i32) -> String {
+    (0..10)
+        .rev()
+        .enumerate()
+        .map(|(idx, bit)| match (idx < 7, seat_id >> bit & 1 == 1) {
+            (true, false) => 'F',
+            (true, true) => 'B',
+            (false, false) => 'L',
+            (false, true) => 'R',
+        })
+        .collect()
+}
+
+/// The seat ID, `row * 8 + column`, of a boarding pass made of seven F/B row
+/// codes followed by three L/R column codes.
+pub fn find_seat_id<T: Int>(boarding_pass: &str) -> Result<T> {
+    for (idx, ch) in boarding_pass.chars().enumerate() {
+        let expected = if idx < 7 { ['F', 'B'] } else { ['L', 'R'] };
+        if idx >= 10 || !expected.contains(&ch) {
+            return Err(Error::parse(format!(
+                "unexpected `{}` in boarding pass, expected 7 F/B then 3 L/R codes",
+                ch
+            ))
+            .at_column(idx + 1)
+            .with_text(boarding_pass));
+        }
+    }
+    let length = boarding_pass.chars().count();
+    if length != 10 {
+        return Err(Error::parse(format!(
+            "boarding pass is {} codes long, expected 10",
+            length
+        ))
+        .with_text(boarding_pass));
+    }
+
+    let (rows, cols): (Vec<char>, Vec<char>) =
+        split_boarding_pass_into_rows_and_cols(boarding_pass);
+    let (row, column) = (
+        find_code(rows, 'F', 'B', 0, 127),
+        find_code(cols, 'L', 'R', 0, 7),
+    );
+    trace!(
+        Info,
+        "seat",
+        pass = boarding_pass,
+        row = row,
+        column = column
+    );
+    T::from(row as u16)
+        .try_mul(T::from(8))?
+        .try_add(T::from(column as u16))
+}
+
+fn split_boarding_pass_into_rows_and_cols(boarding_pass: &str) -> (Vec<char>, Vec<char>) {
+    let (rows, cols): (Vec<char>, Vec<char>) =
+        boarding_pass.chars().partition(|&c| c != 'L' && c != 'R');
+
+    (rows, cols)
+}
+
+fn find_code(
+    code_sequence: Vec<char>,
+    lower_bound_code: char,
+    upper_bound_code: char,
+    mut min_value: i32,
+    mut max_value: i32,
+) -> i32 {
+    let mut last_seen: char = ' ';
+    for ch in code_sequence.iter() {
+        let difference = (max_value - min_value) / 2 + 1;
+        if *ch == lower_bound_code {
+            max_value -= difference;
+        } else if *ch == upper_bound_code {
+            min_value += difference;
+        }
+        trace!(
+            Debug,
+            "bisect",
+            code = *ch,
+            min = min_value,
+            max = max_value
+        );
+        last_seen = *ch;
+    }
+
+    if last_seen == lower_bound_code {
+        min_value
+    } else {
+        max_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Rng;
+    use proptest::prelude::*;
+
+    // The codes are just a ten bit binary number with B and R as ones.
+    fn brute_force_seat_id(boarding_pass: &str) -> i32 {
+        boarding_pass
+            .chars()
+            .fold(0, |id, c| id * 2 + if c == 'B' || c == 'R' { 1 } else { 0 })
+    }
+
+    proptest! {
+        #[test]
+        fn find_seat_id_matches_binary(row in 0..128i32, col in 0..8i32) {
+            let pass = boarding_pass_for(row * 8 + col);
+            prop_assert_eq!(row * 8 + col, find_seat_id(&pass).unwrap());
+            prop_assert_eq!(brute_force_seat_id(&pass), find_seat_id(&pass).unwrap());
+        }
+
+        #[test]
+        fn find_seat_id_rejects_anything_else(pass in "[FBLRX]{0,12}") {
+            let well_formed = pass.len() == 10
+                && pass[..7].chars().all(|c| c == 'F' || c == 'B')
+                && pass[7..].chars().all(|c| c == 'L' || c == 'R');
+            prop_assert_eq!(well_formed, find_seat_id::<i32>(&pass).is_ok());
+        }
+
+        #[test]
+        fn finds_the_missing_seat(
+            first in 1..900i32,
+            len in 3..100i32,
+            offset in 1..99i32,
+            shuffle in any::<prop::sample::Index>(),
+        ) {
+            let missing = first + 1 + offset % (len - 2);
+            let mut seat_ids: Vec<i32> = (first..first + len).filter(|&id| id != missing).collect();
+            let shift = shuffle.index(seat_ids.len());
+            seat_ids.rotate_left(shift);
+            prop_assert_eq!(missing, Day05.part_two(&seat_ids).unwrap());
+            prop_assert_eq!(first + len - 1, Day05.part_one(&seat_ids).unwrap());
+        }
+    }
+
+    #[test]
+    fn can_split_boarding_pass() {
+        let input = String::from("BFFFBBFRRR");
+        let (rows, cols) = split_boarding_pass_into_rows_and_cols(&input);
+        assert_eq!(vec!['B', 'F', 'F', 'F', 'B', 'B', 'F'], rows);
+        assert_eq!(vec!['R', 'R', 'R'], cols);
+    }
+
+    #[test]
+    fn can_find_code() {
+        let mut input = String::from("FBFBBFFRLR");
+        let (rows, cols) = split_boarding_pass_into_rows_and_cols(&input);
+        assert_eq!(44, find_code(rows, 'F', 'B', 0, 127));
+        assert_eq!(5, find_code(cols, 'L', 'R', 0, 7));
+        input = String::from("BFFFBBFRRR");
+        let (rows, cols) = split_boarding_pass_into_rows_and_cols(&input);
+        assert_eq!(70, find_code(rows, 'F', 'B', 0, 127));
+        assert_eq!(7, find_code(cols, 'L', 'R', 0, 7));
+        input = String::from("BBFFBBFRLL");
+        let (rows, cols) = split_boarding_pass_into_rows_and_cols(&input);
+        assert_eq!(102, find_code(rows, 'F', 'B', 0, 127));
+        assert_eq!(4, find_code(cols, 'L', 'R', 0, 7));
+    }
+
+    #[test]
+    fn can_find_seat_id() {
+        assert_eq!(357, find_seat_id("FBFBBFFRLR").unwrap());
+        assert_eq!(567, find_seat_id("BFFFBBFRRR").unwrap());
+        assert_eq!(119, find_seat_id("FFFBBBFRRR").unwrap());
+        assert_eq!(820, find_seat_id::<i32>("BBFFBBFRLL").unwrap());
+        assert_eq!(820u64, find_seat_id("BBFFBBFRLL").unwrap());
+    }
+
+    #[test]
+    fn can_find_highest_and_missing_seat_ids() {
+        let seat_ids = Day05.parse("BFFFBBFRRR\nBFFFBBFRLL\nBFFFBBFRLR\n").unwrap();
+        assert_eq!(567, Day05.part_one(&seat_ids).unwrap());
+        assert_eq!(566, Day05.part_two(&seat_ids).unwrap());
+    }
+
+    #[test]
+    fn can_explain_answers() {
+        let seat_ids = Day05.parse("BFFFBBFRRR\nBFFFBBFRLL\nBFFFBBFRLR\n").unwrap();
+        assert_eq!(
+            "seat 567 is row 70, column 7 (BFFFBBFRRR), the highest of 3 passes",
+            Day05.explain(&seat_ids, 1).unwrap().unwrap()
+        );
+        assert_eq!(
+            "seat 566 is row 70, column 6 (BFFFBBFRRL), free between the taken seats 565 and 567",
+            Day05.explain(&seat_ids, 2).unwrap().unwrap()
+        );
+        assert!(Day05.explain(&vec![], 1).unwrap().is_err());
+    }
+
+    #[test]
+    fn generated_passes_have_one_gap() {
+        for &size in [2, 100, 5000].iter() {
+            let input = Day05.generate(&mut Rng::new(5), size).unwrap();
+            let mut seat_ids = Day05.parse(&input).unwrap();
+            seat_ids.sort_unstable();
+            let gaps: Vec<i32> = seat_ids
+                .windows(2)
+                .filter(|pair| pair[1] != pair[0] + 1)
+                .map(|pair| pair[0] + 1)
+                .collect();
+            assert_eq!(1, gaps.len());
+            assert_eq!(gaps[0], Day05.part_two(&seat_ids).unwrap());
+            assert_eq!(
+                *seat_ids.last().unwrap(),
+                Day05.part_one(&seat_ids).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn reports_bad_boarding_passes() {
+        let error = Day05.parse("BFFFBBFRRR\nBFFXBBFRLL\n").unwrap_err();
+        assert_eq!(Some(2), error.line);
+        assert_eq!(Some(4), error.column);
+        let error = Day05.parse("  FBFXBFFRLR\n").unwrap_err();
+        assert_eq!(Some(6), error.column);
+        assert_eq!(Some("  FBFXBFFRLR".to_string()), error.text);
+        assert!(find_seat_id::<i32>("BFFFBBF").is_err());
+        assert!(find_seat_id::<i32>("BFFFBBFRRRR").is_err());
+        assert!(find_seat_id::<i32>("BFFFBBRFRR").is_err());
+        assert!(Day05.part_one(&vec![]).is_err());
+        assert!(Day05.part_two(&vec![565, 566, 567]).is_err());
+    }
+}
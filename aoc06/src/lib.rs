@@ -0,0 +1,210 @@
+//! Day 6: tallying customs declaration answers by group.
+
+use aoc_core::parse::{self, column_of};
+use aoc_core::{aoc, int, Error, Int, Result, Rng, Solution};
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// The questions each person in a group answered yes to, one set per person.
+pub type Group = Vec<HashSet<char>>;
+
+/// The puzzle, parsed as a list of groups.
+#[derive(Default)]
+pub struct Day06;
+
+#[aoc(day = 6)]
+impl Solution for Day06 {
+    type Input = Vec<Group>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    // Every answer is a question letter from a to z.
+    fn parse(&self, input: &str) -> Result<Vec<Group>> {
+        let mut groups = Vec::new();
+        for group_responses in parse::groups(input) {
+            let mut group = Group::new();
+            for line in group_responses {
+                let answers = line.text.trim();
+                if let Some(column) = answers.chars().position(|c| !c.is_ascii_lowercase()) {
+                    return Err(Error::parse("expected only question letters a-z")
+                        .at_column(column_of(line.text, answers) + column)
+                        .on_line(line.number, line.text));
+                }
+                // A letter written twice is still one yes.
+                group.push(answers.chars().collect());
+            }
+            groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    fn part_one(&self, groups: &Vec<Group>) -> Result<i32> {
+        count_any_yes_responses(groups)
+    }
+
+    fn part_two(&self, groups: &Vec<Group>) -> Result<i32> {
+        count_universal_yes_responses(groups)
+    }
+
+    // Each person answers yes to a random handful of distinct questions.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut questions: Vec<char> = ('a'..='z').collect();
+        let mut groups = Vec::new();
+        for _ in 0..size.max(1) {
+            let mut people = Vec::new();
+            for _ in 0..rng.range(1, 6) {
+                rng.shuffle(&mut questions);
+                let answered = rng.range(1, 27) as usize;
+                people.push(questions[..answered].iter().collect::<String>());
+            }
+            groups.push(people.join("\n"));
+        }
+        Some(groups.join("\n\n") + "\n")
+    }
+}
+
+/// The sum over every group of the questions anyone in it answered yes to.
+pub fn count_any_yes_responses<T: Int>(groups: &[Group]) -> Result<T> {
+    let mut total_responses = T::from(0);
+
+    for group in groups {
+        let mut yes_answers: HashSet<char> = HashSet::new();
+        for person_yes in group {
+            yes_answers.extend(person_yes);
+        }
+        total_responses = total_responses.try_add(int::from_usize(yes_answers.len())?)?;
+    }
+    Ok(total_responses)
+}
+
+/// The sum over every group of the questions everyone in it answered yes to.
+pub fn count_universal_yes_responses<T: Int>(groups: &[Group]) -> Result<T> {
+    let mut total_responses = T::from(0);
+
+    for group in groups {
+        let mut yes_answers: HashMap<char, usize> = HashMap::new();
+        let mut num_people: usize = 0;
+        for person_yes in group {
+            num_people += 1;
+            for &response in person_yes {
+                let counter = yes_answers.entry(response).or_insert(0);
+                *counter += 1;
+            }
+        }
+        let everyone = yes_answers.values().map(|&v| v == num_people);
+        total_responses = total_responses.try_add(int::count(everyone)?)?;
+    }
+    Ok(total_responses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Rng;
+    use indoc::indoc;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    const SAMPLE: &str = include_str!("../input/test.txt");
+
+    fn answers(groups: &[Vec<String>]) -> String {
+        groups
+            .iter()
+            .map(|group| group.join("\n"))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    fn brute_force_counts(groups: &[Vec<String>]) -> (i32, i32) {
+        let mut any = 0;
+        let mut every = 0;
+        for group in groups {
+            let people: Vec<BTreeSet<char>> = group.iter().map(|p| p.chars().collect()).collect();
+            any += ('a'..='z')
+                .filter(|c| people.iter().any(|p| p.contains(c)))
+                .count();
+            every += ('a'..='z')
+                .filter(|c| people.iter().all(|p| p.contains(c)))
+                .count();
+        }
+        (any as i32, every as i32)
+    }
+
+    proptest! {
+        #[test]
+        fn counts_match_brute_force(
+            groups in prop::collection::vec(prop::collection::vec("[a-z]{1,8}", 1..6), 1..8),
+            trailing_newline in any::<bool>(),
+        ) {
+            let mut survey_responses = answers(&groups);
+            if trailing_newline {
+                survey_responses.push('\n');
+            }
+            let (any, every) = brute_force_counts(&groups);
+            let parsed = Day06.parse(&survey_responses).unwrap();
+            prop_assert_eq!(any, count_any_yes_responses(&parsed).unwrap());
+            prop_assert_eq!(every, count_universal_yes_responses(&parsed).unwrap());
+        }
+    }
+
+    #[test]
+    fn can_count_any_yes_responses() {
+        let groups = Day06
+            .parse(indoc! {"
+                abcx
+                abcy
+                abcz
+            "})
+            .unwrap();
+        assert_eq!(6, count_any_yes_responses::<i32>(&groups).unwrap());
+        assert_eq!(
+            11,
+            count_any_yes_responses(&Day06.parse(SAMPLE).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_count_universal_yes_responses() {
+        let groups = Day06
+            .parse(indoc! {"
+                abcx
+                abcy
+                abcz
+            "})
+            .unwrap();
+        assert_eq!(3u64, count_universal_yes_responses(&groups).unwrap());
+        assert_eq!(
+            6,
+            count_universal_yes_responses(&Day06.parse(SAMPLE).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn generated_responses_match_brute_force() {
+        let input = Day06.generate(&mut Rng::new(6), 300).unwrap();
+        let groups: Vec<Vec<String>> = input
+            .split("\n\n")
+            .map(|group| group.lines().map(String::from).collect())
+            .collect();
+        assert_eq!(300, groups.len());
+        let (any, every) = brute_force_counts(&groups);
+        let survey_responses = Day06.parse(&input).unwrap();
+        assert_eq!(any, Day06.part_one(&survey_responses).unwrap());
+        assert_eq!(every, Day06.part_two(&survey_responses).unwrap());
+    }
+
+    #[test]
+    fn reports_bad_responses() {
+        let error = Day06.parse("abc\n\na\nb?\n").unwrap_err();
+        assert_eq!(Some(4), error.line);
+        assert_eq!(Some(2), error.column);
+    }
+
+    #[test]
+    fn can_count_crlf_responses() {
+        let crlf = SAMPLE.replace('\n', "\r\n");
+        let survey_responses = Day06.parse(&crlf).unwrap();
+        assert_eq!(11, Day06.part_one(&survey_responses).unwrap());
+        assert_eq!(6, Day06.part_two(&survey_responses).unwrap());
+    }
+}
@@ -0,0 +1,474 @@
+//! Day 9: breaking the XMAS cipher.
+
+use aoc_core::repl::argument;
+use aoc_core::trace::Value;
+use aoc_core::{aoc, parse, trace, Error, Int, Result, Rng, Solution};
+use std::ops::Range;
+
+/// The puzzle, parsed as the stream of numbers.
+pub struct Day09 {
+    /// How many numbers before each one it has to be a sum of two of; 25 in
+    /// the puzzle, 5 in its example.
+    pub preamble_size: usize,
+}
+
+impl Default for Day09 {
+    fn default() -> Self {
+        Day09 { preamble_size: 25 }
+    }
+}
+
+// The real XMAS stream has values past i32::MAX, so everything is i64.
+#[aoc(day = 9)]
+impl Solution for Day09 {
+    type Input = Vec<i64>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Vec<i64>> {
+        convert_to_vector(input)
+    }
+
+    fn part_one(&self, input_vector: &Vec<i64>) -> Result<i64> {
+        find_first_failing(input_vector, self.preamble_size)
+    }
+
+    fn part_two(&self, input_vector: &Vec<i64>) -> Result<i64> {
+        let first_fail_value = find_first_failing(input_vector, self.preamble_size)?;
+        find_encryption_weakness(input_vector, first_fail_value)
+    }
+
+    // The sample stream only has a five number preamble.
+    fn configure(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "preamble" => {
+                self.preamble_size = value.parse().map_err(|_| {
+                    Error::parse(format!("expected a preamble size, found `{}`", value))
+                })?
+            }
+            _ => return Err(Error::parse(format!("unknown setting `{}`", name))),
+        }
+        Ok(())
+    }
+
+    // Every number is the sum of two small ones from its preamble, apart from
+    // one planted number that is not but does have a contiguous range adding
+    // up to it. Sums keep growing, so a very large `size` stops short where
+    // the numbers would no longer fit.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let preamble = self.preamble_size.max(2);
+        let length = size.max(preamble + 2);
+        let mut stream: Vec<i64> = (1..=4 * preamble as i64).collect();
+        rng.shuffle(&mut stream);
+        stream.truncate(preamble);
+        let planted_at = rng.range(preamble as u64 + 1, length as u64) as usize;
+
+        loop {
+            let window = &stream[stream.len() - preamble..];
+            let mut smallest = window.to_vec();
+            smallest.sort_unstable();
+            smallest.dedup();
+            if stream.len() == planted_at || smallest.len() < 2 || smallest[0] > 1 << 56 {
+                let planted = plant_invalid(rng, &stream, preamble);
+                stream.push(planted);
+                break;
+            }
+            let first = smallest[rng.below(smallest.len().min(3))];
+            let second = loop {
+                let second = smallest[rng.below(smallest.len().min(6))];
+                if second != first {
+                    break second;
+                }
+            };
+            stream.push(first + second);
+        }
+        while stream.len() < length {
+            let window = &stream[stream.len() - preamble..];
+            let first = window[rng.below(preamble)];
+            match window.iter().find(|&&v| v != first && v < 1 << 56) {
+                Some(&second) if first < 1 << 56 => stream.push(first + second),
+                _ => break,
+            }
+        }
+        Some(stream.iter().map(|v| format!("{}\n", v)).collect())
+    }
+
+    fn explain(&self, input_vector: &Vec<i64>, part: u8) -> Option<Result<String>> {
+        let idx = first_failing_position(input_vector, self.preamble_size)?;
+        let invalid = input_vector[idx];
+        if part == 1 {
+            return Some(Ok(format!(
+                "{} at position {} is not the sum of two of the {} numbers before it",
+                invalid, idx, self.preamble_size
+            )));
+        }
+        let range = match find_weakness_range(input_vector, invalid) {
+            Ok(range) => range,
+            Err(e) => return Some(Err(e)),
+        };
+        let numbers = &input_vector[range.clone()];
+        let terms: Vec<String> = numbers.iter().map(|v| v.to_string()).collect();
+        Some(Ok(format!(
+            "positions {} to {} add up to {}: {}\nthe answer is the smallest {} plus the largest {}",
+            range.start,
+            range.end - 1,
+            invalid,
+            terms.join(" + "),
+            numbers.iter().min().unwrap(),
+            numbers.iter().max().unwrap()
+        )))
+    }
+
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![(
+            "window <start> <end>",
+            "check the number at <end> against the ones from <start>, counting from 0",
+        )]
+    }
+
+    fn explore(&self, input_vector: &Vec<i64>, words: &[&str]) -> Option<Result<String>> {
+        match words[0] {
+            "window" => Some(explore_window(input_vector, words)),
+            _ => None,
+        }
+    }
+}
+
+fn explore_window(input_vector: &[i64], words: &[&str]) -> Result<String> {
+    let start: usize = argument(words, 1, "start")?;
+    let end: usize = argument(words, 2, "end")?;
+    if start >= end || end >= input_vector.len() {
+        return Err(Error::parse(format!(
+            "need start < end < {}, the length of the stream",
+            input_vector.len()
+        )));
+    }
+    let window: Vec<String> = input_vector[start..end]
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+    let verdict = if is_valid(input_vector, end - start, end) {
+        "valid"
+    } else {
+        "invalid"
+    };
+    Ok(format!(
+        "{}\n{} at {} is {}",
+        window.join(" "),
+        input_vector[end],
+        end,
+        verdict
+    ))
+}
+
+// A contiguous run of at least two numbers whose sum no two preamble
+// numbers make. The last preamble + 1 numbers always work as they add up
+// to more than any pair of them.
+fn plant_invalid(rng: &mut Rng, stream: &[i64], preamble: usize) -> i64 {
+    let window = &stream[stream.len() - preamble..];
+    let is_pair_sum = |sum: i64| {
+        window
+            .iter()
+            .any(|&v| sum - v != v && window.contains(&(sum - v)))
+    };
+    for _ in 0..20 {
+        let start = rng.below(stream.len() - 1);
+        let end = (start + 2 + rng.below(8)).min(stream.len());
+        let sum: i64 = stream[start..end].iter().sum();
+        if !is_pair_sum(sum) {
+            return sum;
+        }
+    }
+    stream[stream.len() - preamble - 1..].iter().sum()
+}
+
+/// The first number after the preamble that is not the sum of two different
+/// numbers among the `preamble_size` before it.
+pub fn find_first_failing<T: Int>(input_vector: &[T], preamble_size: usize) -> Result<T> {
+    match first_failing_position(input_vector, preamble_size) {
+        Some(idx) => {
+            trace!(
+                Info,
+                "first invalid",
+                position = idx,
+                value = Value::number(input_vector[idx])
+            );
+            Ok(input_vector[idx])
+        }
+        None => Err(Error::no_solution(format!(
+            "every number after the {} number preamble is valid",
+            preamble_size
+        ))),
+    }
+}
+
+/// Where [`find_first_failing`] finds its number.
+pub fn first_failing_position<T: Int>(input_vector: &[T], preamble_size: usize) -> Option<usize> {
+    (preamble_size..input_vector.len()).find(|&idx| !is_valid(input_vector, preamble_size, idx))
+}
+
+/// The smallest plus the largest number of the run that adds up to
+/// `target_val`.
+pub fn find_encryption_weakness<T: Int>(input_vector: &[T], target_val: T) -> Result<T> {
+    let range = &input_vector[find_weakness_range(input_vector, target_val)?];
+    range
+        .iter()
+        .min()
+        .unwrap()
+        .try_add(*range.iter().max().unwrap())
+}
+
+/// The run of numbers that adds up to `target_val`.
+///
+/// The range has to be at least two numbers long, otherwise the invalid
+/// number would always be its own weakness.
+pub fn find_weakness_range<T: Int>(input_vector: &[T], target_val: T) -> Result<Range<usize>> {
+    for idx in 0..input_vector.len() {
+        let mut sum = T::from(0);
+        let mut len: usize = 0;
+        for &v in input_vector.iter().skip(idx) {
+            let next = sum.try_add(v)?;
+            if next > target_val {
+                break;
+            }
+            sum = next;
+            len += 1;
+        }
+        if sum == target_val && len >= 2 {
+            return Ok(idx..idx + len);
+        }
+    }
+
+    Err(Error::no_solution(format!(
+        "no contiguous range of at least two numbers sums to {}",
+        target_val
+    )))
+}
+
+/// Reads one number per line.
+pub fn convert_to_vector<T: Int>(input: &str) -> Result<Vec<T>> {
+    parse::numbers(input)
+}
+
+/// Whether the number at `check_position` is the sum of two different
+/// numbers among the `preamble_size` before it.
+///
+/// A needle that does not fit in `T` cannot be in the preamble either.
+pub fn is_valid<T: Int>(input_list: &[T], preamble_size: usize, check_position: usize) -> bool {
+    let start = check_position - preamble_size;
+    let preamble_list = &input_list[start..check_position];
+    let valid = preamble_list.iter().any(|&preamble_value| {
+        match input_list[check_position].checked_sub(preamble_value) {
+            Some(needle) => needle != preamble_value && preamble_list.contains(&needle),
+            None => false,
+        }
+    });
+    trace!(
+        Debug,
+        "window",
+        position = check_position,
+        value = Value::number(input_list[check_position]),
+        start = start,
+        valid = valid
+    );
+    valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Rng;
+    use indoc::indoc;
+    use proptest::prelude::*;
+
+    const SAMPLE: &str = include_str!("../input/test.txt");
+
+    fn brute_force_is_valid(preamble: &[i64], val: i64) -> bool {
+        (0..preamble.len()).any(|i| {
+            (i + 1..preamble.len())
+                .any(|j| preamble[i] != preamble[j] && preamble[i] + preamble[j] == val)
+        })
+    }
+
+    fn brute_force_first_failing(stream: &[i64], preamble_size: usize) -> Option<i64> {
+        (preamble_size..stream.len())
+            .find(|&idx| !brute_force_is_valid(&stream[idx - preamble_size..idx], stream[idx]))
+            .map(|idx| stream[idx])
+    }
+
+    fn brute_force_weakness(stream: &[i64], target_val: i64) -> Option<i64> {
+        for start in 0..stream.len() {
+            for end in start + 2..=stream.len() {
+                let range = &stream[start..end];
+                if range.iter().sum::<i64>() == target_val {
+                    return Some(range.iter().min().unwrap() + range.iter().max().unwrap());
+                }
+            }
+        }
+        None
+    }
+
+    proptest! {
+        #[test]
+        fn find_first_failing_matches_brute_force(
+            stream in prop::collection::vec(1..40i64, 0..40),
+            preamble_size in 2..6usize,
+        ) {
+            let expected = brute_force_first_failing(&stream, preamble_size);
+            prop_assert_eq!(expected, find_first_failing(&stream, preamble_size).ok());
+        }
+
+        #[test]
+        fn find_encryption_weakness_matches_brute_force(
+            stream in prop::collection::vec(1..50i64, 0..30),
+            target_val in 1..200i64,
+        ) {
+            let expected = brute_force_weakness(&stream, target_val);
+            prop_assert_eq!(expected, find_encryption_weakness(&stream, target_val).ok());
+        }
+    }
+
+    #[test]
+    fn can_find_valid_values() {
+        let input = SAMPLE;
+        let preamble: Vec<i64> = convert_to_vector(input).unwrap();
+        assert!(!is_valid(&preamble, 5, 14)); // 127
+        assert!(is_valid(&preamble, 5, 10)); // 102
+        assert!(is_valid(&preamble, 5, 19)); // 576
+        assert!(!is_valid(&[10, 3, 7], 2, 2)); // 10 - 3 is not a sum
+    }
+
+    #[test]
+    fn can_find_first_failing() {
+        assert_eq!(
+            100,
+            find_first_failing(
+                &convert_to_vector(indoc! {"
+                    1
+                    2
+                    3
+                    4
+                    5
+                    6
+                    7
+                    8
+                    9
+                    10
+                    11
+                    12
+                    13
+                    14
+                    15
+                    16
+                    17
+                    18
+                    19
+                    20
+                    21
+                    22
+                    23
+                    24
+                    25
+                    26
+                    49
+                    50
+                    100
+                "})
+                .unwrap(),
+                25
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            127,
+            find_first_failing(&convert_to_vector(SAMPLE).unwrap(), 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_find_encryption_weakness() {
+        assert_eq!(
+            62,
+            find_encryption_weakness(&convert_to_vector(SAMPLE).unwrap(), 127).unwrap()
+        );
+    }
+
+    #[test]
+    fn generated_stream_has_the_planted_number() {
+        for seed in 0..20 {
+            let mut day = Day09::default();
+            if seed % 2 == 0 {
+                Solution::configure(&mut day, "preamble", "5").unwrap();
+            }
+            let input = day.generate(&mut Rng::new(seed), 200).unwrap();
+            let stream = day.parse(&input).unwrap();
+            let invalid = brute_force_first_failing(&stream, day.preamble_size).unwrap();
+            assert_eq!(invalid, day.part_one(&stream).unwrap());
+            assert_eq!(
+                brute_force_weakness(&stream, invalid),
+                day.part_two(&stream).ok()
+            );
+        }
+    }
+
+    #[test]
+    fn can_explain_answers() {
+        let mut day = Day09::default();
+        Solution::configure(&mut day, "preamble", "5").unwrap();
+        let stream = day.parse(SAMPLE).unwrap();
+        assert_eq!(
+            "127 at position 14 is not the sum of two of the 5 numbers before it",
+            day.explain(&stream, 1).unwrap().unwrap()
+        );
+        assert_eq!(
+            "positions 2 to 5 add up to 127: 15 + 25 + 47 + 40\nthe answer is the smallest 15 plus the largest 47",
+            day.explain(&stream, 2).unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn can_explore_windows() {
+        let stream = Day09::default().parse(SAMPLE).unwrap();
+        let explore = |words: &[&str]| Day09::default().explore(&stream, words).unwrap();
+        assert_eq!(
+            "95 102 117 150 182\n127 at 14 is invalid",
+            explore(&["window", "9", "14"]).unwrap()
+        );
+        assert_eq!(
+            "35 20 15 25 47\n40 at 5 is valid",
+            explore(&["window", "0", "5"]).unwrap()
+        );
+        assert!(explore(&["window", "5", "5"]).is_err());
+        assert!(explore(&["window", "0", "20"]).is_err());
+    }
+
+    #[test]
+    fn reports_bad_streams() {
+        let error = convert_to_vector::<i64>("35\n20\nfifteen\n").unwrap_err();
+        assert_eq!(Some(3), error.line);
+        assert!(find_first_failing(&[1, 2, 3], 2).is_err());
+        assert!(find_encryption_weakness(&[1, 2, 3], 100).is_err());
+        assert!(find_encryption_weakness(&[1, 127, 3], 127).is_err());
+    }
+
+    #[test]
+    fn works_in_any_width_without_overflowing() {
+        let stream: Vec<u64> = convert_to_vector(SAMPLE).unwrap();
+        assert_eq!(127, find_first_failing(&stream, 5).unwrap());
+        assert_eq!(62, find_encryption_weakness(&stream, 127).unwrap());
+
+        let huge: Vec<i128> =
+            convert_to_vector("1\n170141183460469231731687303715884105000\n3\n").unwrap();
+        assert_eq!(1, huge[0]);
+        assert!(convert_to_vector::<i64>("1\n9223372036854775808\n").is_err());
+
+        // Wrapping arithmetic would make i64::MIN the sum of -1 and i64::MAX.
+        assert!(!is_valid(&[-1, i64::MAX, i64::MIN], 2, 2));
+        let error = find_weakness_range(&[i64::MIN, -1, 5], 4).unwrap_err();
+        assert!(
+            error.to_string().contains("does not fit in i64"),
+            "{}",
+            error
+        );
+    }
+}